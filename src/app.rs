@@ -0,0 +1,99 @@
+//! Multi-window application bookkeeping.
+
+use std::collections::HashMap;
+
+use windows::Win32::Foundation::HWND;
+
+use crate::event_loop::{Event, EventLoop};
+use crate::window::Window;
+
+/// Tracks several [`Window`]s as a single multi-window application, routing
+/// per-`HWND` events to the right window so multi-document apps don't need
+/// their own `HWND` -> [`Window`] bookkeeping.
+#[derive(Default)]
+pub struct WindowSet {
+    windows: HashMap<isize, Entry>,
+}
+
+struct Entry {
+    window: Window,
+    event_loop: EventLoop,
+}
+
+impl WindowSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts tracking `window`.
+    pub fn insert(&mut self, window: Window) {
+        self.windows.insert(
+            window.hwnd().0 as isize,
+            Entry {
+                window,
+                event_loop: EventLoop::new(),
+            },
+        );
+    }
+
+    /// Stops tracking and returns the window for `hwnd`, if any. Dropping
+    /// the returned `Window` destroys its `HWND`.
+    pub fn remove(&mut self, hwnd: HWND) -> Option<Window> {
+        self.windows
+            .remove(&(hwnd.0 as isize))
+            .map(|entry| entry.window)
+    }
+
+    /// Returns a reference to the tracked window for `hwnd`, if any.
+    pub fn get(&self, hwnd: HWND) -> Option<&Window> {
+        self.windows
+            .get(&(hwnd.0 as isize))
+            .map(|entry| &entry.window)
+    }
+
+    /// Returns `true` if no windows remain in the set.
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// The number of windows currently tracked.
+    pub fn len(&self) -> usize {
+        self.windows.len()
+    }
+
+    /// Drains pending messages for every tracked window without blocking,
+    /// dispatching each as a typed [`Event`] to `callback` alongside the
+    /// `HWND` it originated from. A window that reports
+    /// `Event::CloseRequested` is removed from the set (and so destroyed)
+    /// once `callback` has observed the event. Returns `false` once the
+    /// last tracked window has closed, so the caller knows to stop its own
+    /// loop.
+    pub fn pump_events(&mut self, mut callback: impl FnMut(HWND, Event)) -> bool {
+        let raw_hwnds: Vec<isize> = self.windows.keys().copied().collect();
+        let mut closed = Vec::new();
+
+        for raw in raw_hwnds {
+            let Some(entry) = self.windows.get_mut(&raw) else {
+                continue;
+            };
+            let hwnd = entry.window.hwnd();
+
+            let still_running = entry.event_loop.pump_events(&entry.window, |event| {
+                if event == Event::CloseRequested {
+                    closed.push(raw);
+                }
+                callback(hwnd, event);
+            });
+            if !still_running {
+                return false;
+            }
+        }
+
+        for raw in closed {
+            self.windows.remove(&raw);
+        }
+
+        !self.windows.is_empty()
+    }
+}
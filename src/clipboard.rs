@@ -0,0 +1,351 @@
+//! Reading and writing text, images, and file lists on the system
+//! clipboard.
+
+use std::path::PathBuf;
+
+use windows::Win32::Foundation::{GlobalFree, HANDLE, HGLOBAL};
+use windows::Win32::Graphics::Gdi::{BITMAPINFOHEADER, BI_RGB};
+use windows::Win32::System::DataExchange::{
+    CloseClipboard, EmptyClipboard, GetClipboardData, OpenClipboard, SetClipboardData,
+};
+use windows::Win32::System::Memory::{
+    GlobalAlloc, GlobalLock, GlobalSize, GlobalUnlock, GMEM_MOVEABLE,
+};
+use windows::Win32::System::Ole::{CF_DIB, CF_HDROP, CF_UNICODETEXT};
+use windows::Win32::UI::Shell::{DragQueryFileW, HDROP};
+
+use crate::error::{Error, Result};
+
+/// A decoded bitmap read from the clipboard via [`get_image`], as top-down,
+/// row-major straight-alpha RGBA8 pixel data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// The image's width, in pixels.
+    pub width: i32,
+    /// The image's height, in pixels.
+    pub height: i32,
+    /// Row-major, top-to-bottom, straight-alpha RGBA8 pixel data.
+    pub pixels: Vec<u8>,
+}
+
+/// Returns the clipboard's current contents as text, or `None` if the
+/// clipboard does not hold Unicode text.
+pub fn get_text() -> Result<Option<String>> {
+    // SAFETY: `None` opens the clipboard for the current task rather than
+    // associating it with a particular window.
+    unsafe { OpenClipboard(None) }.map_err(Error::from)?;
+
+    let text = get_text_locked();
+
+    // SAFETY: the clipboard was opened by the `OpenClipboard` call above and
+    // is closed exactly once here.
+    unsafe {
+        let _ = CloseClipboard();
+    }
+
+    text
+}
+
+/// Reads the clipboard's Unicode text contents, assuming it is already
+/// open. Split out so every early return still runs through
+/// [`get_text`]'s single `CloseClipboard` call.
+fn get_text_locked() -> Result<Option<String>> {
+    // SAFETY: the clipboard is open for the duration of this call.
+    let handle = match unsafe { GetClipboardData(CF_UNICODETEXT.0 as u32) } {
+        Ok(handle) => handle,
+        Err(_) => return Ok(None),
+    };
+
+    // SAFETY: `handle` was just returned by `GetClipboardData` and names a
+    // global memory object owned by the clipboard for as long as it remains
+    // open.
+    let ptr = unsafe { GlobalLock(HGLOBAL(handle.0)) };
+    if ptr.is_null() {
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+
+    // SAFETY: `ptr` is valid for `GlobalSize(handle)` bytes while locked,
+    // and `CF_UNICODETEXT` data is a null-terminated UTF-16 string.
+    let text = unsafe {
+        let len = GlobalSize(HGLOBAL(handle.0)) / std::mem::size_of::<u16>();
+        let slice = std::slice::from_raw_parts(ptr.cast::<u16>(), len);
+        let end = slice.iter().position(|&c| c == 0).unwrap_or(slice.len());
+        String::from_utf16_lossy(&slice[..end])
+    };
+
+    // SAFETY: `handle` was locked immediately above.
+    unsafe {
+        let _ = GlobalUnlock(HGLOBAL(handle.0));
+    }
+
+    Ok(Some(text))
+}
+
+/// Replaces the clipboard's contents with `text`.
+pub fn set_text(text: &str) -> Result<()> {
+    let mut utf16: Vec<u16> = text.encode_utf16().collect();
+    utf16.push(0);
+    let byte_len = utf16.len() * std::mem::size_of::<u16>();
+
+    // SAFETY: `byte_len` is a valid, non-zero allocation size.
+    let hmem = unsafe { GlobalAlloc(GMEM_MOVEABLE, byte_len) }.map_err(Error::from)?;
+
+    // SAFETY: `hmem` was just allocated above with room for `byte_len`
+    // bytes.
+    let ptr = unsafe { GlobalLock(hmem) };
+    if ptr.is_null() {
+        unsafe {
+            let _ = GlobalFree(Some(hmem));
+        }
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+    // SAFETY: `ptr` is valid for `byte_len` bytes while locked, matching
+    // `utf16`'s length.
+    unsafe {
+        std::ptr::copy_nonoverlapping(utf16.as_ptr(), ptr.cast::<u16>(), utf16.len());
+        let _ = GlobalUnlock(hmem);
+    }
+
+    // SAFETY: `None` opens the clipboard for the current task rather than
+    // associating it with a particular window.
+    unsafe { OpenClipboard(None) }.map_err(Error::from)?;
+
+    let result = set_text_locked(hmem);
+
+    // SAFETY: the clipboard was opened by the `OpenClipboard` call above and
+    // is closed exactly once here.
+    unsafe {
+        let _ = CloseClipboard();
+    }
+
+    result
+}
+
+/// Hands ownership of `hmem` to the clipboard, assuming it is already open.
+/// Split out so every early return still runs through [`set_text`]'s single
+/// `CloseClipboard` call.
+fn set_text_locked(hmem: HGLOBAL) -> Result<()> {
+    set_global_locked(CF_UNICODETEXT.0 as u32, hmem)
+}
+
+/// Empties the clipboard and hands ownership of `hmem` to it under `format`,
+/// freeing `hmem` itself if either step fails. Shared by every `set_*`
+/// function so each only has to build its own global-memory buffer.
+fn set_global_locked(format: u32, hmem: HGLOBAL) -> Result<()> {
+    // SAFETY: the clipboard is open for the duration of this call.
+    if let Err(err) = unsafe { EmptyClipboard() } {
+        unsafe {
+            let _ = GlobalFree(Some(hmem));
+        }
+        return Err(Error::from(err));
+    }
+
+    // SAFETY: `hmem` is a global memory handle allocated with `GMEM_MOVEABLE`,
+    // matching what `SetClipboardData` expects to take ownership of.
+    if let Err(err) = unsafe { SetClipboardData(format, Some(HANDLE(hmem.0))) } {
+        unsafe {
+            let _ = GlobalFree(Some(hmem));
+        }
+        return Err(Error::from(err));
+    }
+
+    Ok(())
+}
+
+/// Returns the clipboard's current contents as an [`Image`], or `None` if
+/// the clipboard does not hold a device-independent bitmap. Only 32bpp
+/// uncompressed (`BI_RGB`) bitmaps are decoded; any other format also
+/// yields `None`.
+pub fn get_image() -> Result<Option<Image>> {
+    // SAFETY: `None` opens the clipboard for the current task rather than
+    // associating it with a particular window.
+    unsafe { OpenClipboard(None) }.map_err(Error::from)?;
+
+    let image = get_image_locked();
+
+    // SAFETY: the clipboard was opened by the `OpenClipboard` call above and
+    // is closed exactly once here.
+    unsafe {
+        let _ = CloseClipboard();
+    }
+
+    image
+}
+
+/// Reads the clipboard's `CF_DIB` contents, assuming it is already open.
+/// Split out so every early return still runs through [`get_image`]'s
+/// single `CloseClipboard` call.
+fn get_image_locked() -> Result<Option<Image>> {
+    // SAFETY: the clipboard is open for the duration of this call.
+    let handle = match unsafe { GetClipboardData(CF_DIB.0 as u32) } {
+        Ok(handle) => handle,
+        Err(_) => return Ok(None),
+    };
+
+    // SAFETY: `handle` was just returned by `GetClipboardData` and names a
+    // global memory object owned by the clipboard for as long as it remains
+    // open.
+    let ptr = unsafe { GlobalLock(HGLOBAL(handle.0)) };
+    if ptr.is_null() {
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+
+    // SAFETY: a `CF_DIB` global memory block begins with a
+    // `BITMAPINFOHEADER`.
+    let header = unsafe { *ptr.cast::<BITMAPINFOHEADER>() };
+    if header.biBitCount != 32 || header.biCompression != BI_RGB.0 {
+        // SAFETY: `handle` was locked immediately above.
+        unsafe {
+            let _ = GlobalUnlock(HGLOBAL(handle.0));
+        }
+        return Ok(None);
+    }
+
+    // SAFETY: the header describes the pixel data which immediately
+    // follows it (plus any color table, which 32bpp `BI_RGB` data, as
+    // checked above, has none of).
+    let image = unsafe {
+        let width = header.biWidth;
+        let height = header.biHeight.abs();
+
+        let pixels_ptr = ptr.byte_add(header.biSize as usize).cast::<u8>();
+        let row_len = (width * 4) as usize;
+        let mut pixels = vec![0u8; row_len * height as usize];
+        for y in 0..height as usize {
+            let src_row = if header.biHeight < 0 {
+                y
+            } else {
+                height as usize - 1 - y
+            };
+            let src = std::slice::from_raw_parts(pixels_ptr.add(src_row * row_len), row_len);
+            pixels[y * row_len..(y + 1) * row_len].copy_from_slice(src);
+        }
+        // DIB pixels are stored as BGRA; swap to RGBA.
+        for px in pixels.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    };
+
+    // SAFETY: `handle` was locked immediately above.
+    unsafe {
+        let _ = GlobalUnlock(HGLOBAL(handle.0));
+    }
+
+    Ok(Some(image))
+}
+
+/// Replaces the clipboard's contents with a `width` x `height` bitmap, given
+/// as top-down, row-major straight-alpha RGBA8 pixel data.
+///
+/// Panics if `pixels` is shorter than `width * height * 4` bytes.
+pub fn set_image(width: i32, height: i32, pixels: &[u8]) -> Result<()> {
+    let row_len = (width * 4) as usize;
+    assert!(pixels.len() >= row_len * height as usize);
+
+    let header = BITMAPINFOHEADER {
+        biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+        biWidth: width,
+        biHeight: -height,
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB.0,
+        ..Default::default()
+    };
+    let byte_len = header.biSize as usize + row_len * height as usize;
+
+    // SAFETY: `byte_len` is a valid, non-zero allocation size.
+    let hmem = unsafe { GlobalAlloc(GMEM_MOVEABLE, byte_len) }.map_err(Error::from)?;
+
+    // SAFETY: `hmem` was just allocated above with room for `byte_len`
+    // bytes.
+    let ptr = unsafe { GlobalLock(hmem) };
+    if ptr.is_null() {
+        unsafe {
+            let _ = GlobalFree(Some(hmem));
+        }
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+    // SAFETY: `ptr` is valid for `byte_len` bytes while locked, matching the
+    // header followed by `row_len * height` bytes of pixel data.
+    unsafe {
+        ptr.cast::<BITMAPINFOHEADER>().write(header);
+        let pixels_ptr = ptr.byte_add(header.biSize as usize).cast::<u8>();
+        std::ptr::copy_nonoverlapping(pixels.as_ptr(), pixels_ptr, row_len * height as usize);
+        // DIB pixels are stored as BGRA; swap from RGBA in place.
+        let dst = std::slice::from_raw_parts_mut(pixels_ptr, row_len * height as usize);
+        for px in dst.chunks_exact_mut(4) {
+            px.swap(0, 2);
+        }
+        let _ = GlobalUnlock(hmem);
+    }
+
+    // SAFETY: `None` opens the clipboard for the current task rather than
+    // associating it with a particular window.
+    unsafe { OpenClipboard(None) }.map_err(Error::from)?;
+
+    let result = set_global_locked(CF_DIB.0 as u32, hmem);
+
+    // SAFETY: the clipboard was opened by the `OpenClipboard` call above and
+    // is closed exactly once here.
+    unsafe {
+        let _ = CloseClipboard();
+    }
+
+    result
+}
+
+/// Returns the list of file paths on the clipboard, or `None` if the
+/// clipboard does not hold a file list.
+pub fn get_file_list() -> Result<Option<Vec<PathBuf>>> {
+    // SAFETY: `None` opens the clipboard for the current task rather than
+    // associating it with a particular window.
+    unsafe { OpenClipboard(None) }.map_err(Error::from)?;
+
+    let files = get_file_list_locked();
+
+    // SAFETY: the clipboard was opened by the `OpenClipboard` call above and
+    // is closed exactly once here.
+    unsafe {
+        let _ = CloseClipboard();
+    }
+
+    files
+}
+
+/// Reads the clipboard's `CF_HDROP` contents, assuming it is already open.
+/// Split out so every early return still runs through
+/// [`get_file_list`]'s single `CloseClipboard` call.
+fn get_file_list_locked() -> Result<Option<Vec<PathBuf>>> {
+    // SAFETY: the clipboard is open for the duration of this call.
+    let handle = match unsafe { GetClipboardData(CF_HDROP.0 as u32) } {
+        Ok(handle) => handle,
+        Err(_) => return Ok(None),
+    };
+    let hdrop = HDROP(handle.0);
+
+    // SAFETY: `hdrop` was just returned by `GetClipboardData` and names a
+    // drop handle owned by the clipboard for as long as it remains open.
+    let count = unsafe { DragQueryFileW(hdrop, u32::MAX, None) };
+
+    let mut files = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        // SAFETY: `hdrop` is still valid; calling with a `None` buffer
+        // returns the required length, excluding the null terminator.
+        let len = unsafe { DragQueryFileW(hdrop, i, None) };
+        let mut buf = vec![0u16; len as usize + 1];
+        // SAFETY: `buf` has room for `len` characters plus a null
+        // terminator, matching what `DragQueryFileW` will write.
+        unsafe { DragQueryFileW(hdrop, i, Some(&mut buf)) };
+        files.push(PathBuf::from(String::from_utf16_lossy(
+            &buf[..len as usize],
+        )));
+    }
+
+    Ok(Some(files))
+}
@@ -0,0 +1,235 @@
+//! Native Win32 controls (`BUTTON`, `EDIT`, `STATIC`, `COMBOBOX`) hosted as
+//! children of a skylight [`Window`], with `WM_COMMAND` notifications
+//! routed through [`Window::drain_control_events`] and
+//! [`crate::handler::WindowHandler::on_control_event`] rather than
+//! requiring callers to handle the raw message themselves, and with a
+//! DPI-aware font applied automatically.
+
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Graphics::Gdi::{CreateFontIndirectW, DeleteObject, HFONT};
+use windows::Win32::UI::HiDpi::SystemParametersInfoForDpi;
+use windows::Win32::UI::Input::KeyboardAndMouse::EnableWindow;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DestroyWindow, GetWindowTextLengthW, GetWindowTextW, SendMessageW,
+    SetWindowTextW, HMENU, NONCLIENTMETRICSW, SPI_GETNONCLIENTMETRICS, WM_SETFONT, WS_CHILD,
+    WS_TABSTOP, WS_VISIBLE,
+};
+
+use crate::error::{Error, Result};
+use crate::geometry::{Point2D, Size2D};
+use crate::menu::MenuId;
+use crate::util::encode_wide;
+use crate::window::Window;
+
+/// The Win32 window class backing a [`Control`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlKind {
+    /// A push button (`BUTTON`, `BS_PUSHBUTTON`).
+    Button,
+    /// A single-line text entry field (`EDIT`).
+    Edit,
+    /// A static text label (`STATIC`).
+    Static,
+    /// A drop-down list (`COMBOBOX`).
+    ComboBox,
+}
+
+impl ControlKind {
+    fn class_name(self) -> &'static str {
+        match self {
+            ControlKind::Button => "BUTTON",
+            ControlKind::Edit => "EDIT",
+            ControlKind::Static => "STATIC",
+            ControlKind::ComboBox => "COMBOBOX",
+        }
+    }
+}
+
+/// A native Win32 control hosted as a child of a skylight [`Window`],
+/// created via [`Window::control`].
+///
+/// Dropping a `Control` destroys it. This is optional: destroying the
+/// parent [`Window`] destroys every child control along with it, since
+/// they are created with `WS_CHILD`.
+pub struct Control {
+    hwnd: HWND,
+    font: HFONT,
+}
+
+impl Control {
+    /// The control's raw window handle, e.g. for APIs skylight doesn't
+    /// itself wrap.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd
+    }
+
+    /// Sets the control's text (a button's label, an edit's contents, a
+    /// static's caption, ...).
+    pub fn set_text(&self, text: &str) -> Result<()> {
+        let text = encode_wide(text);
+        // SAFETY: `self.hwnd` is a valid, live control and `text` is a
+        // null-terminated string valid for the duration of the call.
+        unsafe { SetWindowTextW(self.hwnd, PCWSTR(text.as_ptr())) }.map_err(Error::from)
+    }
+
+    /// Reads the control's current text.
+    pub fn text(&self) -> String {
+        // SAFETY: `self.hwnd` is a valid, live control.
+        let len = unsafe { GetWindowTextLengthW(self.hwnd) };
+        if len <= 0 {
+            return String::new();
+        }
+
+        let mut buf = vec![0u16; len as usize + 1];
+        // SAFETY: `buf` is large enough to hold the text plus its null
+        // terminator, and `self.hwnd` is a valid, live control.
+        let copied = unsafe { GetWindowTextW(self.hwnd, &mut buf) };
+        String::from_utf16_lossy(&buf[..copied as usize])
+    }
+
+    /// Enables or disables the control.
+    pub fn set_enabled(&self, enabled: bool) {
+        // SAFETY: `self.hwnd` is a valid, live control.
+        unsafe {
+            EnableWindow(self.hwnd, enabled);
+        }
+    }
+}
+
+impl Drop for Control {
+    fn drop(&mut self) {
+        // SAFETY: `self.hwnd` was created by `ControlBuilder::build` and has
+        // not yet been destroyed.
+        unsafe {
+            let _ = DestroyWindow(self.hwnd);
+        }
+        // SAFETY: `self.font` was created by `ControlBuilder::build` for
+        // this control alone and is no longer referenced once it is
+        // destroyed above.
+        unsafe {
+            let _ = DeleteObject(self.font.into());
+        }
+    }
+}
+
+/// Builds a [`Control`] as a child of a skylight [`Window`], created via
+/// [`Window::control`].
+pub struct ControlBuilder<'a, Id: MenuId> {
+    kind: ControlKind,
+    parent: &'a Window,
+    id: Id,
+    text: String,
+    position: Point2D<i32>,
+    size: Size2D<i32>,
+}
+
+impl<'a, Id: MenuId> ControlBuilder<'a, Id> {
+    fn new(kind: ControlKind, parent: &'a Window, id: Id) -> Self {
+        Self {
+            kind,
+            parent,
+            id,
+            text: String::new(),
+            position: Point2D::new(0, 0),
+            size: Size2D::new(100, 24),
+        }
+    }
+
+    /// Sets the control's initial text.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets the control's position within its parent's client area, in
+    /// logical pixels.
+    pub fn with_position(mut self, position: Point2D<i32>) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Sets the control's size, in logical pixels.
+    pub fn with_size(mut self, size: Size2D<i32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Creates the control.
+    pub fn build(self) -> Result<Control> {
+        let dpi = self.parent.dpi();
+        let class_name = encode_wide(self.kind.class_name());
+        let text = encode_wide(&self.text);
+
+        // SAFETY: `self.parent.hwnd()` is a valid, live window; `class_name`
+        // and `text` are null-terminated strings valid for the duration of
+        // the call.
+        let hwnd = unsafe {
+            CreateWindowExW(
+                Default::default(),
+                PCWSTR(class_name.as_ptr()),
+                PCWSTR(text.as_ptr()),
+                WS_CHILD | WS_VISIBLE | WS_TABSTOP,
+                dpi.scale(self.position.x),
+                dpi.scale(self.position.y),
+                dpi.scale(self.size.width),
+                dpi.scale(self.size.height),
+                Some(self.parent.hwnd()),
+                Some(HMENU(self.id.into_raw() as isize as *mut _)),
+                None,
+                None,
+            )
+        }
+        .map_err(Error::from)?;
+
+        let font = dpi_aware_font(dpi.value())?;
+        // SAFETY: `hwnd` was just created above and `font` stays alive for
+        // as long as `hwnd` does, since it is owned by the returned
+        // `Control` and destroyed alongside it.
+        unsafe {
+            SendMessageW(
+                hwnd,
+                WM_SETFONT,
+                Some(windows::Win32::Foundation::WPARAM(font.0 as usize)),
+                Some(windows::Win32::Foundation::LPARAM(1)),
+            );
+        }
+
+        Ok(Control { hwnd, font })
+    }
+}
+
+impl Window {
+    /// Starts building a native control of `kind`, identified by `id` for
+    /// [`Window::drain_control_events`] and
+    /// [`crate::handler::WindowHandler::on_control_event`].
+    pub fn control<Id: MenuId>(&self, kind: ControlKind, id: Id) -> ControlBuilder<'_, Id> {
+        ControlBuilder::new(kind, self, id)
+    }
+}
+
+/// Builds a font matching the current system message-box font, scaled for
+/// `dpi`, for use as a control's `WM_SETFONT` font so its text doesn't
+/// look undersized next to the rest of the window's DPI-aware UI.
+fn dpi_aware_font(dpi: u32) -> Result<HFONT> {
+    let mut metrics = NONCLIENTMETRICSW {
+        cbSize: std::mem::size_of::<NONCLIENTMETRICSW>() as u32,
+        ..Default::default()
+    };
+
+    // SAFETY: `metrics` is a valid out-parameter of the size declared in
+    // `cbSize`.
+    unsafe {
+        SystemParametersInfoForDpi(
+            SPI_GETNONCLIENTMETRICS.0,
+            metrics.cbSize,
+            Some((&mut metrics as *mut NONCLIENTMETRICSW).cast()),
+            0,
+            dpi,
+        )
+    }
+    .map_err(Error::from)?;
+
+    // SAFETY: `metrics.lfMessageFont` was just filled in above.
+    Ok(unsafe { CreateFontIndirectW(&metrics.lfMessageFont) })
+}
@@ -0,0 +1,4 @@
+//! Test and diagnostic tooling that isn't needed by applications embedding
+//! this crate, but is useful when developing it or reproducing input bugs.
+
+pub mod recorder;
@@ -0,0 +1,174 @@
+//! Captures a live stream of raw window messages to a fixture file, and
+//! replays such a fixture back into a [`Keyboard`] or [`Mouse`], so input
+//! bugs (IME quirks, layout-specific regressions, ...) observed in a real
+//! window can be turned into a reproducible test.
+//!
+//! The on-disk format is a plain text file, one message per line, of the
+//! form `msg wparam lparam`. It isn't meant to be read by anything outside
+//! this crate; it exists purely to avoid hand-writing message arrays.
+
+use std::io::{self, BufRead, Write};
+
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+
+use crate::keyboard::Keyboard;
+use crate::mouse::Mouse;
+
+/// A single raw window message, as passed to `Keyboard::process_evt` or
+/// `Mouse::process_evt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordedMessage {
+    pub msg: u32,
+    pub wparam: usize,
+    pub lparam: isize,
+}
+
+/// Accumulates [`RecordedMessage`]s as they're observed, for later dumping
+/// to a fixture file with [`Recorder::dump`].
+#[derive(Debug, Default)]
+pub struct Recorder {
+    messages: Vec<RecordedMessage>,
+}
+
+impl Recorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a raw window message to the recording.
+    pub fn record(&mut self, msg: u32, wparam: WPARAM, lparam: LPARAM) {
+        self.messages.push(RecordedMessage {
+            msg,
+            wparam: wparam.0,
+            lparam: lparam.0,
+        });
+    }
+
+    /// Writes the recording to `writer` in the fixture text format, one
+    /// message per line.
+    pub fn dump(&self, mut writer: impl Write) -> io::Result<()> {
+        for message in &self.messages {
+            writeln!(
+                writer,
+                "{} {} {}",
+                message.msg, message.wparam, message.lparam
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays a fixture recorded by [`Recorder::dump`] into `keyboard`.
+pub fn replay_into_keyboard(reader: impl BufRead, keyboard: &mut Keyboard) -> io::Result<()> {
+    for message in parse(reader)? {
+        keyboard.process_evt(message.msg, WPARAM(message.wparam), LPARAM(message.lparam));
+    }
+    Ok(())
+}
+
+/// Replays a fixture recorded by [`Recorder::dump`] into `mouse`.
+pub fn replay_into_mouse(reader: impl BufRead, mouse: &mut Mouse) -> io::Result<()> {
+    for message in parse(reader)? {
+        mouse.process_evt(message.msg, WPARAM(message.wparam), LPARAM(message.lparam));
+    }
+    Ok(())
+}
+
+/// Parses the fixture text format back into [`RecordedMessage`]s.
+fn parse(reader: impl BufRead) -> io::Result<Vec<RecordedMessage>> {
+    let mut messages = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed recorded message");
+
+        let msg = fields
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+        let wparam = fields
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+        let lparam = fields
+            .next()
+            .ok_or_else(invalid)?
+            .parse()
+            .map_err(|_| invalid())?;
+
+        messages.push(RecordedMessage {
+            msg,
+            wparam,
+            lparam,
+        });
+    }
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_then_parse_round_trips() {
+        let mut recorder = Recorder::new();
+        recorder.record(0x100, WPARAM(0x41), LPARAM(1));
+        recorder.record(0x201, WPARAM(0), LPARAM(-1));
+
+        let mut buf = Vec::new();
+        recorder.dump(&mut buf).unwrap();
+
+        let messages = parse(buf.as_slice()).unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                RecordedMessage {
+                    msg: 0x100,
+                    wparam: 0x41,
+                    lparam: 1,
+                },
+                RecordedMessage {
+                    msg: 0x201,
+                    wparam: 0,
+                    lparam: -1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_skips_blank_lines() {
+        let messages = parse("1 2 3\n\n   \n4 5 6\n".as_bytes()).unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                RecordedMessage {
+                    msg: 1,
+                    wparam: 2,
+                    lparam: 3,
+                },
+                RecordedMessage {
+                    msg: 4,
+                    wparam: 5,
+                    lparam: 6,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        assert!(parse("1 2".as_bytes()).is_err());
+        assert!(parse("1 2 notanumber".as_bytes()).is_err());
+    }
+}
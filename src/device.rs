@@ -0,0 +1,52 @@
+//! Device arrival/removal notifications delivered via `WM_DEVICECHANGE`, so
+//! apps can react to HID devices, audio devices, and removable drives
+//! hotplugging without parsing `DEV_BROADCAST_*` structures by hand.
+
+use windows::core::GUID;
+use windows::Win32::Devices::HumanInterfaceDevice::GUID_DEVINTERFACE_HID;
+use windows::Win32::Media::KernelStreaming::KSCATEGORY_AUDIO;
+use windows::Win32::System::Ioctl::GUID_DEVINTERFACE_VOLUME;
+
+/// A class of device interface a window can subscribe to via
+/// [`crate::window::Builder::with_device_notifications`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceClass {
+    /// Human interface devices, e.g. keyboards, mice, and game controllers.
+    Hid,
+    /// Audio endpoint devices, e.g. speakers and microphones.
+    Audio,
+    /// Removable drive volumes, e.g. USB flash drives.
+    Volume,
+}
+
+impl DeviceClass {
+    /// The device interface class GUID this variant registers for.
+    pub(crate) fn guid(self) -> GUID {
+        match self {
+            Self::Hid => GUID_DEVINTERFACE_HID,
+            Self::Audio => KSCATEGORY_AUDIO,
+            Self::Volume => GUID_DEVINTERFACE_VOLUME,
+        }
+    }
+
+    /// Maps a `DEV_BROADCAST_DEVICEINTERFACE_W::dbcc_classguid` back to the
+    /// [`DeviceClass`] that registered for it, if any.
+    pub(crate) fn from_guid(guid: GUID) -> Option<Self> {
+        match guid {
+            GUID_DEVINTERFACE_HID => Some(Self::Hid),
+            KSCATEGORY_AUDIO => Some(Self::Audio),
+            GUID_DEVINTERFACE_VOLUME => Some(Self::Volume),
+            _ => None,
+        }
+    }
+}
+
+/// A device arrival/removal notification reported by `WM_DEVICECHANGE`, for
+/// the [`DeviceClass`] a window subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceEvent {
+    /// A device of this class arrived.
+    Arrived,
+    /// A device of this class was removed.
+    Removed,
+}
@@ -0,0 +1,449 @@
+//! Themed dialog box wrappers: [`message_box`], a safe wrapper over
+//! `MessageBoxW` that is modal to a skylight [`Window`] rather than to the
+//! whole desktop, and the [`FileOpen`]/[`FileSave`] builders wrapping the
+//! modern `IFileOpenDialog`/`IFileSaveDialog` COM dialogs.
+
+#[cfg(feature = "file_dialog")]
+use std::path::{Path, PathBuf};
+
+use windows::core::PCWSTR;
+#[cfg(feature = "file_dialog")]
+use windows::Win32::Foundation::ERROR_CANCELLED;
+#[cfg(feature = "file_dialog")]
+use windows::Win32::System::Com::{CoCreateInstance, CoTaskMemFree, CLSCTX_INPROC_SERVER};
+#[cfg(feature = "file_dialog")]
+use windows::Win32::UI::Shell::Common::COMDLG_FILTERSPEC;
+#[cfg(feature = "file_dialog")]
+use windows::Win32::UI::Shell::{
+    FileOpenDialog, FileSaveDialog, IFileDialog, IFileOpenDialog, IFileSaveDialog, IShellItem,
+    SHCreateItemFromParsingName, FOS_ALLOWMULTISELECT, SIGDN_FILESYSPATH,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, IDABORT, IDCANCEL, IDIGNORE, IDNO, IDOK, IDRETRY, IDYES, MB_ABORTRETRYIGNORE,
+    MB_ICONERROR, MB_ICONINFORMATION, MB_ICONQUESTION, MB_ICONWARNING, MB_OK, MB_OKCANCEL,
+    MB_RETRYCANCEL, MB_YESNO, MB_YESNOCANCEL, MESSAGEBOX_RESULT, MESSAGEBOX_STYLE,
+};
+
+use crate::error::{Error, Result};
+use crate::util::encode_wide;
+use crate::window::Window;
+
+/// The set of buttons [`message_box`] shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBoxButtons {
+    /// A single "OK" button.
+    Ok,
+    /// "OK" and "Cancel".
+    OkCancel,
+    /// "Yes" and "No".
+    YesNo,
+    /// "Yes", "No", and "Cancel".
+    YesNoCancel,
+    /// "Retry" and "Cancel".
+    RetryCancel,
+    /// "Abort", "Retry", and "Ignore".
+    AbortRetryIgnore,
+}
+
+impl From<MessageBoxButtons> for MESSAGEBOX_STYLE {
+    fn from(buttons: MessageBoxButtons) -> Self {
+        match buttons {
+            MessageBoxButtons::Ok => MB_OK,
+            MessageBoxButtons::OkCancel => MB_OKCANCEL,
+            MessageBoxButtons::YesNo => MB_YESNO,
+            MessageBoxButtons::YesNoCancel => MB_YESNOCANCEL,
+            MessageBoxButtons::RetryCancel => MB_RETRYCANCEL,
+            MessageBoxButtons::AbortRetryIgnore => MB_ABORTRETRYIGNORE,
+        }
+    }
+}
+
+/// The icon [`message_box`] shows alongside its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBoxIcon {
+    /// No icon.
+    None,
+    /// The "i" information icon.
+    Information,
+    /// The triangular "!" warning icon.
+    Warning,
+    /// The "x" error icon.
+    Error,
+    /// The "?" question icon.
+    Question,
+}
+
+impl From<MessageBoxIcon> for MESSAGEBOX_STYLE {
+    fn from(icon: MessageBoxIcon) -> Self {
+        match icon {
+            MessageBoxIcon::None => MESSAGEBOX_STYLE(0),
+            MessageBoxIcon::Information => MB_ICONINFORMATION,
+            MessageBoxIcon::Warning => MB_ICONWARNING,
+            MessageBoxIcon::Error => MB_ICONERROR,
+            MessageBoxIcon::Question => MB_ICONQUESTION,
+        }
+    }
+}
+
+/// The button the user chose in a [`message_box`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageBoxResult {
+    /// "OK".
+    Ok,
+    /// "Cancel".
+    Cancel,
+    /// "Yes".
+    Yes,
+    /// "No".
+    No,
+    /// "Retry".
+    Retry,
+    /// "Abort".
+    Abort,
+    /// "Ignore".
+    Ignore,
+}
+
+impl MessageBoxResult {
+    fn from_raw(result: MESSAGEBOX_RESULT) -> Result<Self> {
+        match result {
+            IDOK => Ok(Self::Ok),
+            IDCANCEL => Ok(Self::Cancel),
+            IDYES => Ok(Self::Yes),
+            IDNO => Ok(Self::No),
+            IDRETRY => Ok(Self::Retry),
+            IDABORT => Ok(Self::Abort),
+            IDIGNORE => Ok(Self::Ignore),
+            // `MessageBoxW` returns 0 only on failure, e.g. out of memory
+            // or an invalid owner window; everything else is covered above.
+            _ => Err(Error::from(windows::core::Error::from_win32())),
+        }
+    }
+}
+
+/// Shows a modal message box over `owner`, returning the button the user
+/// chose. Wraps `MessageBoxW`, passing `owner`'s `HWND` so the dialog is
+/// modal to it (disabling its input until dismissed) rather than to the
+/// whole desktop, without requiring unsafe calls from user code.
+pub fn message_box(
+    owner: &Window,
+    title: &str,
+    body: &str,
+    buttons: MessageBoxButtons,
+    icon: MessageBoxIcon,
+) -> Result<MessageBoxResult> {
+    let style = MESSAGEBOX_STYLE::from(buttons) | MESSAGEBOX_STYLE::from(icon);
+    let title = encode_wide(title);
+    let body = encode_wide(body);
+
+    // SAFETY: `owner.hwnd()` is a valid, live window; `title` and `body`
+    // are null-terminated strings valid for the duration of the call.
+    let result = unsafe {
+        MessageBoxW(
+            Some(owner.hwnd()),
+            PCWSTR(body.as_ptr()),
+            PCWSTR(title.as_ptr()),
+            style,
+        )
+    };
+
+    MessageBoxResult::from_raw(result)
+}
+
+/// A name/pattern pair shown in a [`FileOpen`]/[`FileSave`] dialog's file
+/// type dropdown, e.g. `("Text Files", "*.txt")`.
+#[cfg(feature = "file_dialog")]
+pub type FileFilter = (String, String);
+
+/// Builds a dialog that lets the user choose one or more existing files to
+/// open, via `IFileOpenDialog`.
+#[cfg(feature = "file_dialog")]
+pub struct FileOpen {
+    title: String,
+    filters: Vec<FileFilter>,
+    default_folder: Option<PathBuf>,
+    multi_select: bool,
+}
+
+#[cfg(feature = "file_dialog")]
+impl FileOpen {
+    /// Starts building a new "open file" dialog.
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            filters: Vec::new(),
+            default_folder: None,
+            multi_select: false,
+        }
+    }
+
+    /// Sets the dialog's title bar text.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Adds a file type filter, shown in the dialog's type dropdown.
+    pub fn with_filter(mut self, name: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.filters.push((name.into(), pattern.into()));
+        self
+    }
+
+    /// Sets the folder the dialog opens to if the user hasn't navigated
+    /// elsewhere since it was last shown.
+    pub fn with_default_folder(mut self, folder: impl Into<PathBuf>) -> Self {
+        self.default_folder = Some(folder.into());
+        self
+    }
+
+    /// Allows the user to select more than one file. Defaults to `false`.
+    pub fn with_multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+
+    /// Shows the dialog modally over `owner`, returning the chosen files,
+    /// or `None` if the user cancelled. Unless [`FileOpen::with_multi_select`]
+    /// was set, the returned `Vec` holds at most one path.
+    pub fn show(self, owner: &Window) -> Result<Option<Vec<PathBuf>>> {
+        with_com(|| {
+            // SAFETY: always safe to call; `FileOpenDialog` identifies the
+            // in-process COM class implementing `IFileOpenDialog`.
+            let dialog: IFileOpenDialog =
+                unsafe { CoCreateInstance(&FileOpenDialog, None, CLSCTX_INPROC_SERVER) }
+                    .map_err(Error::from)?;
+
+            if self.multi_select {
+                // SAFETY: `dialog` is a freshly created, valid instance.
+                let options = unsafe { dialog.GetOptions() }.map_err(Error::from)?;
+                unsafe { dialog.SetOptions(options | FOS_ALLOWMULTISELECT) }
+                    .map_err(Error::from)?;
+            }
+
+            configure(
+                &dialog,
+                &self.title,
+                &self.filters,
+                self.default_folder.as_deref(),
+            )?;
+
+            // SAFETY: `owner.hwnd()` is a valid window for the duration of
+            // the call, which blocks until the dialog is dismissed.
+            match unsafe { dialog.Show(Some(owner.hwnd())) } {
+                Ok(()) => {}
+                Err(err) if is_cancelled(&err) => return Ok(None),
+                Err(err) => return Err(Error::from(err)),
+            }
+
+            // SAFETY: `dialog` just returned successfully from `Show`.
+            let items = unsafe { dialog.GetResults() }.map_err(Error::from)?;
+            // SAFETY: `items` was just obtained above.
+            let count = unsafe { items.GetCount() }.map_err(Error::from)?;
+
+            let mut paths = Vec::with_capacity(count as usize);
+            for index in 0..count {
+                // SAFETY: `index` is within `0..count`.
+                let item = unsafe { items.GetItemAt(index) }.map_err(Error::from)?;
+                paths.push(shell_item_path(&item)?);
+            }
+
+            Ok(Some(paths))
+        })
+    }
+}
+
+#[cfg(feature = "file_dialog")]
+impl Default for FileOpen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds a dialog that lets the user choose a destination to save a file
+/// to, via `IFileSaveDialog`.
+#[cfg(feature = "file_dialog")]
+pub struct FileSave {
+    title: String,
+    filters: Vec<FileFilter>,
+    default_folder: Option<PathBuf>,
+    default_file_name: String,
+}
+
+#[cfg(feature = "file_dialog")]
+impl FileSave {
+    /// Starts building a new "save file" dialog.
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            filters: Vec::new(),
+            default_folder: None,
+            default_file_name: String::new(),
+        }
+    }
+
+    /// Sets the dialog's title bar text.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Adds a file type filter, shown in the dialog's type dropdown.
+    pub fn with_filter(mut self, name: impl Into<String>, pattern: impl Into<String>) -> Self {
+        self.filters.push((name.into(), pattern.into()));
+        self
+    }
+
+    /// Sets the folder the dialog opens to if the user hasn't navigated
+    /// elsewhere since it was last shown.
+    pub fn with_default_folder(mut self, folder: impl Into<PathBuf>) -> Self {
+        self.default_folder = Some(folder.into());
+        self
+    }
+
+    /// Sets the file name initially shown in the dialog's file name field.
+    pub fn with_default_file_name(mut self, name: impl Into<String>) -> Self {
+        self.default_file_name = name.into();
+        self
+    }
+
+    /// Shows the dialog modally over `owner`, returning the chosen
+    /// destination, or `None` if the user cancelled.
+    pub fn show(self, owner: &Window) -> Result<Option<PathBuf>> {
+        with_com(|| {
+            // SAFETY: always safe to call; `FileSaveDialog` identifies the
+            // in-process COM class implementing `IFileSaveDialog`.
+            let dialog: IFileSaveDialog =
+                unsafe { CoCreateInstance(&FileSaveDialog, None, CLSCTX_INPROC_SERVER) }
+                    .map_err(Error::from)?;
+
+            configure(
+                &dialog,
+                &self.title,
+                &self.filters,
+                self.default_folder.as_deref(),
+            )?;
+
+            if !self.default_file_name.is_empty() {
+                let name = encode_wide(&self.default_file_name);
+                // SAFETY: `dialog` is a freshly created, valid instance and
+                // `name` is a null-terminated string valid for the call.
+                unsafe { dialog.SetFileName(PCWSTR(name.as_ptr())) }.map_err(Error::from)?;
+            }
+
+            // SAFETY: `owner.hwnd()` is a valid window for the duration of
+            // the call, which blocks until the dialog is dismissed.
+            match unsafe { dialog.Show(Some(owner.hwnd())) } {
+                Ok(()) => {}
+                Err(err) if is_cancelled(&err) => return Ok(None),
+                Err(err) => return Err(Error::from(err)),
+            }
+
+            // SAFETY: `dialog` just returned successfully from `Show`.
+            let item = unsafe { dialog.GetResult() }.map_err(Error::from)?;
+            Ok(Some(shell_item_path(&item)?))
+        })
+    }
+}
+
+#[cfg(feature = "file_dialog")]
+impl Default for FileSave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Applies the title, filters, and default folder shared by both
+/// [`FileOpen`] and [`FileSave`] to the underlying `IFileDialog`.
+#[cfg(feature = "file_dialog")]
+fn configure(
+    dialog: &IFileDialog,
+    title: &str,
+    filters: &[FileFilter],
+    default_folder: Option<&Path>,
+) -> Result<()> {
+    if !title.is_empty() {
+        let title = encode_wide(title);
+        // SAFETY: `dialog` is a freshly created, valid instance and `title`
+        // is a null-terminated string valid for the call.
+        unsafe { dialog.SetTitle(PCWSTR(title.as_ptr())) }.map_err(Error::from)?;
+    }
+
+    if !filters.is_empty() {
+        let encoded: Vec<(Vec<u16>, Vec<u16>)> = filters
+            .iter()
+            .map(|(name, pattern)| (encode_wide(name), encode_wide(pattern)))
+            .collect();
+        let specs: Vec<COMDLG_FILTERSPEC> = encoded
+            .iter()
+            .map(|(name, pattern)| COMDLG_FILTERSPEC {
+                pszName: PCWSTR(name.as_ptr()),
+                pszSpec: PCWSTR(pattern.as_ptr()),
+            })
+            .collect();
+        // SAFETY: `specs` and the `encoded` buffers it points into are both
+        // still alive.
+        unsafe { dialog.SetFileTypes(&specs) }.map_err(Error::from)?;
+    }
+
+    if let Some(folder) = default_folder {
+        let item = shell_item_from_path(folder)?;
+        // SAFETY: `dialog` is a freshly created, valid instance and `item`
+        // is a valid shell item.
+        unsafe { dialog.SetFolder(&item) }.map_err(Error::from)?;
+    }
+
+    Ok(())
+}
+
+/// Resolves `path` to an `IShellItem`, e.g. for `IFileDialog::SetFolder`.
+#[cfg(feature = "file_dialog")]
+fn shell_item_from_path(path: &Path) -> Result<IShellItem> {
+    let encoded = encode_wide(&path.to_string_lossy());
+    // SAFETY: `encoded` is a null-terminated string valid for the duration
+    // of the call.
+    unsafe { SHCreateItemFromParsingName(PCWSTR(encoded.as_ptr()), None) }.map_err(Error::from)
+}
+
+/// Reads an `IShellItem`'s file system path.
+#[cfg(feature = "file_dialog")]
+fn shell_item_path(item: &IShellItem) -> Result<PathBuf> {
+    // SAFETY: `item` is a valid shell item returned by a file dialog.
+    let name = unsafe { item.GetDisplayName(SIGDN_FILESYSPATH) }.map_err(Error::from)?;
+
+    // SAFETY: `name` was just allocated by `GetDisplayName` and is a valid,
+    // null-terminated string until freed below.
+    let path = String::from_utf16_lossy(unsafe { name.as_wide() });
+
+    // SAFETY: `name` was allocated by `GetDisplayName` via `CoTaskMemAlloc`
+    // and must be freed with `CoTaskMemFree` exactly once.
+    unsafe { CoTaskMemFree(Some(name.0 as *const _)) };
+
+    Ok(PathBuf::from(path))
+}
+
+/// Returns `true` if `err` is the `HRESULT` `IModalWindow::Show` returns
+/// when the user dismissed the dialog without making a choice.
+#[cfg(feature = "file_dialog")]
+fn is_cancelled(err: &windows::core::Error) -> bool {
+    err.code() == windows::core::HRESULT::from_win32(ERROR_CANCELLED.0)
+}
+
+/// Initializes OLE for the duration of `f`, matching
+/// [`crate::window::Window::start_drag`]'s use of the same refcounted
+/// `OleInitialize`/`OleUninitialize` pair, since the file dialogs are COM
+/// objects just like OLE drag-and-drop.
+#[cfg(feature = "file_dialog")]
+fn with_com<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    // SAFETY: `OleInitialize` is refcounted per-thread; it is always safe
+    // to call and is matched by the `OleUninitialize` call below.
+    unsafe { windows::Win32::System::Ole::OleInitialize(None) }.map_err(Error::from)?;
+
+    let result = f();
+
+    // SAFETY: matches the `OleInitialize` call above.
+    unsafe {
+        windows::Win32::System::Ole::OleUninitialize();
+    }
+
+    result
+}
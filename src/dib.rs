@@ -0,0 +1,52 @@
+//! Shared helper for building a top-down 32bpp DIB section from RGBA pixel
+//! data, used by every "paint raw pixels into a GDI bitmap" API
+//! ([`crate::window::CustomCursor::from_rgba`],
+//! [`crate::shell::Icon::from_rgba`],
+//! [`crate::window::Window::set_alpha_bitmap`]), each of which applies a
+//! different per-pixel RGBA -> BGRA conversion once the buffer is ready to
+//! write into.
+
+use windows::Win32::Graphics::Gdi::{
+    CreateDIBSection, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, HBITMAP,
+};
+
+use crate::error::{Error, Result};
+
+/// Creates a `width` x `height` top-down 32bpp DIB section and returns its
+/// handle along with a pointer to its writable backing buffer, sized for
+/// exactly `width * height * 4` bytes.
+///
+/// Panics if `width`/`height` aren't positive or `pixels_len` is shorter
+/// than `width * height * 4` bytes, so callers can safely build a slice of
+/// that length over the returned pointer.
+pub(crate) fn rgba_to_bgra_dib(
+    width: i32,
+    height: i32,
+    pixels_len: usize,
+) -> Result<(HBITMAP, *mut u8)> {
+    assert!(width > 0 && height > 0 && pixels_len >= (width as usize) * (height as usize) * 4);
+
+    let info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // Negative height selects a top-down DIB, matching the row
+            // order callers fill it in.
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut bits = std::ptr::null_mut();
+    // SAFETY: `info` describes a `width` x `height` 32bpp top-down DIB;
+    // `bits` receives a pointer to a writable buffer of that size, owned
+    // by the returned bitmap.
+    let bitmap = unsafe { CreateDIBSection(None, &info, DIB_RGB_COLORS, &mut bits, None, 0) }
+        .map_err(Error::from)?;
+
+    Ok((bitmap, bits.cast::<u8>()))
+}
@@ -0,0 +1,54 @@
+//! DPI detection and scale-factor helpers.
+//!
+//! Win32 reports DPI per-monitor; [`Dpi`] is a thin wrapper around the raw
+//! "dots per inch" value with conversions to the scale factor most UI code
+//! actually wants.
+
+use windows::Win32::Foundation::HWND;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
+
+/// The DPI Windows uses as its 100% scaling baseline.
+pub const DEFAULT_DPI: u32 = 96;
+
+/// A window's dots-per-inch value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dpi(u32);
+
+impl Dpi {
+    /// Queries the current DPI for a live window.
+    ///
+    /// # Safety
+    ///
+    /// `hwnd` must be a valid, non-destroyed window handle.
+    pub(crate) fn detect(hwnd: HWND) -> Self {
+        // SAFETY: `hwnd` is required by the caller to be a valid window.
+        let dpi = unsafe { GetDpiForWindow(hwnd) };
+        Self::from_raw(dpi)
+    }
+
+    /// Wraps a raw DPI value, e.g. the new DPI reported by `WM_DPICHANGED`.
+    pub(crate) fn from_raw(dpi: u32) -> Self {
+        Self(if dpi == 0 { DEFAULT_DPI } else { dpi })
+    }
+
+    /// The raw dots-per-inch value, e.g. `96` or `144`.
+    pub const fn value(self) -> u32 {
+        self.0
+    }
+
+    /// The scale factor relative to the 96 DPI baseline, e.g. `1.0` or `1.5`.
+    pub fn scale_factor(self) -> f64 {
+        f64::from(self.0) / f64::from(DEFAULT_DPI)
+    }
+
+    /// Scales a logical (96 DPI) pixel value up to physical pixels at this DPI.
+    pub fn scale(self, logical: i32) -> i32 {
+        ((logical as i64 * self.0 as i64) / i64::from(DEFAULT_DPI)) as i32
+    }
+}
+
+impl Default for Dpi {
+    fn default() -> Self {
+        Self(DEFAULT_DPI)
+    }
+}
@@ -0,0 +1,16 @@
+//! Crate-wide error type.
+
+/// Errors which can occur when creating or interacting with native Win32
+/// resources.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A Win32 API call returned a failure `HRESULT`.
+    #[error("Win32 API call failed: {0}")]
+    Win32(#[from] windows::core::Error),
+    /// Reading a file supplied by the caller (e.g. an icon file) failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Convenience alias for crate results.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,382 @@
+//! A typed event loop built on top of the Win32 message pump, so
+//! applications don't need to hand-roll `GetMessageW`/`TranslateMessage`
+//! loops themselves.
+
+use std::time::{Duration, Instant};
+
+use windows::Win32::Foundation::{HANDLE, WAIT_FAILED, WAIT_TIMEOUT};
+use windows::Win32::UI::Input::KeyboardAndMouse::EnableWindow;
+use windows::Win32::UI::WindowsAndMessaging::{
+    DispatchMessageW, GetMessageW, KillTimer, MsgWaitForMultipleObjectsEx, PeekMessageW, SetTimer,
+    TranslateMessage, MSG, MWMO_INPUTAVAILABLE, PM_REMOVE, QS_ALLINPUT, WM_QUIT, WM_TIMER,
+};
+
+use crate::dpi::Dpi;
+use crate::error::{Error, Result};
+use crate::geometry::Size2D;
+use crate::keyboard::KeyCode;
+use crate::window::{Window, WindowState};
+
+/// Controls how [`EventLoop::run`] waits between iterations of the message
+/// pump, chosen by the callback passed to `run`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ControlFlow {
+    /// Drain pending messages and return immediately, without waiting for
+    /// new ones; suitable for apps that redraw every frame.
+    Poll,
+    /// Block until the next window message arrives.
+    Wait,
+    /// Block until either the next window message arrives or `Instant` is
+    /// reached, whichever comes first.
+    WaitUntil(Instant),
+    /// Stop the loop after the current iteration.
+    Exit,
+}
+
+/// A typed window event dispatched by [`EventLoop::run`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Event {
+    /// The user requested that the window be closed.
+    CloseRequested,
+    /// The window needs to be repainted.
+    Paint,
+    /// The window's client area changed size.
+    Resized(Size2D<i32>),
+    /// A key was pressed or released.
+    Key {
+        /// The key that transitioned.
+        code: KeyCode,
+        /// `true` if the key was pressed, `false` if released.
+        pressed: bool,
+    },
+    /// The window was minimized, maximized, or restored.
+    StateChanged(WindowState),
+    /// The window's DPI changed, e.g. after being dragged to a monitor
+    /// with a different scale factor. Carries the new DPI.
+    DpiChanged(Dpi),
+    /// The system theme or high-contrast setting changed.
+    ThemeChanged,
+    /// The window gained or lost keyboard focus.
+    FocusChanged(bool),
+    /// The user began dragging a window edge or the title bar, entering
+    /// the modal resize/move loop.
+    EnterSizeMove,
+    /// The modal resize/move loop entered via [`Event::EnterSizeMove`]
+    /// ended.
+    ExitSizeMove,
+    /// The OS is ending the session (shutdown, restart, or log off) and the
+    /// window did not veto it via
+    /// [`WindowHandler::on_shutdown_requested`](crate::handler::WindowHandler::on_shutdown_requested).
+    /// Handlers run synchronously before this event is even reported, so by
+    /// the time it's observed here any last-chance saving should already be
+    /// underway; call [`Window::block_shutdown`] to hold the session open
+    /// briefly while that finishes.
+    ShutdownRequested,
+}
+
+/// Why [`EventLoop::wait_for_handles`] returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitWake {
+    /// A window message arrived and was dispatched.
+    Message,
+    /// The wait handle at this index into the slice passed to
+    /// [`EventLoop::wait_for_handles`] became signaled.
+    Handle(usize),
+    /// The call's timeout elapsed without a message or handle firing.
+    Timeout,
+}
+
+/// Pumps Win32 messages for a [`Window`] and dispatches them to a callback
+/// as typed [`Event`]s.
+#[derive(Debug, Default)]
+pub struct EventLoop {
+    close_reported: bool,
+    shutdown_reported: bool,
+    last_paint_generation: u64,
+    last_dpi_generation: u64,
+    last_theme_change_generation: u64,
+    last_focused: bool,
+}
+
+impl EventLoop {
+    /// Creates a new event loop.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs the message pump for `window`, invoking `callback` with every
+    /// dispatched [`Event`]. `callback` sets `control_flow` to choose how
+    /// the loop waits before its next iteration; the loop returns once
+    /// `control_flow` is set to [`ControlFlow::Exit`] or the window is
+    /// destroyed.
+    pub fn run(&mut self, window: &Window, mut callback: impl FnMut(Event, &mut ControlFlow)) {
+        let mut control_flow = ControlFlow::Wait;
+
+        loop {
+            if !self.pump_once(control_flow) {
+                return;
+            }
+
+            for event in self.drain_events(window) {
+                callback(event, &mut control_flow);
+                if control_flow == ControlFlow::Exit {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Drains every message currently queued for `window` without
+    /// blocking, dispatching each as a typed [`Event`] to `callback`, then
+    /// returns immediately so the caller can render a frame. Intended for
+    /// game-loop-style apps that want to pump input before every frame
+    /// rather than hand control to [`EventLoop::run`]'s callback-driven
+    /// model. Returns `false` once `WM_QUIT` is received, at which point
+    /// the caller should stop its loop.
+    pub fn pump_events(&mut self, window: &Window, mut callback: impl FnMut(Event)) -> bool {
+        if !drain_pending() {
+            return false;
+        }
+
+        for event in self.drain_events(window) {
+            callback(event);
+        }
+
+        true
+    }
+
+    /// Blocks until either a window message arrives, one of `handles`
+    /// becomes signaled, or `timeout` elapses (`None` waits indefinitely),
+    /// then drains and dispatches any pending messages as typed [`Event`]s
+    /// to `callback`. Lets an application integrate IO completion handles,
+    /// timers, or other waitable objects into the message loop without
+    /// busy polling. Returns `Ok(None)` once `WM_QUIT` is received, at
+    /// which point the caller should stop its loop.
+    pub fn wait_for_handles(
+        &mut self,
+        window: &Window,
+        handles: &[HANDLE],
+        timeout: Option<Duration>,
+        mut callback: impl FnMut(Event),
+    ) -> Result<Option<WaitWake>> {
+        let elapse_ms = match timeout {
+            Some(duration) => duration.as_millis().clamp(0, u32::MAX as u128) as u32,
+            None => u32::MAX,
+        };
+
+        // SAFETY: `handles` is a valid slice of live wait handles for the
+        // duration of the call.
+        let result = unsafe {
+            MsgWaitForMultipleObjectsEx(Some(handles), elapse_ms, QS_ALLINPUT, MWMO_INPUTAVAILABLE)
+        };
+
+        let wake = match result {
+            WAIT_FAILED => return Err(Error::from(windows::core::Error::from_win32())),
+            WAIT_TIMEOUT => WaitWake::Timeout,
+            result if (result.0 as usize) < handles.len() => WaitWake::Handle(result.0 as usize),
+            _ => WaitWake::Message,
+        };
+
+        if !drain_pending() {
+            return Ok(None);
+        }
+
+        for event in self.drain_events(window) {
+            callback(event);
+        }
+
+        Ok(Some(wake))
+    }
+
+    /// Runs a nested modal message loop for `window`, disabling `owner`
+    /// (via `EnableWindow`) for the duration so its input is blocked while
+    /// the dialog is up, and re-enabling it before returning regardless of
+    /// how the loop ends. Intended for settings/confirmation dialogs built
+    /// with skylight windows. `callback` is invoked with every dispatched
+    /// [`Event`] and returns `Some(result)` once it has a final answer, at
+    /// which point the loop exits on the same iteration; closing `window`
+    /// (or setting `control_flow` to [`ControlFlow::Exit`]) without ever
+    /// returning a result ends the loop with `None`, as if the dialog were
+    /// cancelled.
+    pub fn run_modal<T>(
+        &mut self,
+        window: &Window,
+        owner: &Window,
+        mut callback: impl FnMut(Event, &mut ControlFlow) -> Option<T>,
+    ) -> Option<T> {
+        // SAFETY: `owner.hwnd()` is a valid window for the duration of the
+        // call.
+        unsafe {
+            EnableWindow(owner.hwnd(), false);
+        }
+
+        let mut control_flow = ControlFlow::Wait;
+        let mut result = None;
+
+        'modal: loop {
+            if !self.pump_once(control_flow) {
+                break;
+            }
+
+            for event in self.drain_events(window) {
+                let close_requested = event == Event::CloseRequested;
+                if let Some(value) = callback(event, &mut control_flow) {
+                    result = Some(value);
+                    break 'modal;
+                }
+                if close_requested || control_flow == ControlFlow::Exit {
+                    break 'modal;
+                }
+            }
+        }
+
+        // SAFETY: `owner.hwnd()` is still a valid window; re-enabling it is
+        // always safe.
+        unsafe {
+            EnableWindow(owner.hwnd(), true);
+        }
+
+        result
+    }
+
+    /// Waits for and dispatches messages according to `control_flow`.
+    /// Returns `false` if a `WM_QUIT` was received and the loop should
+    /// stop.
+    fn pump_once(&self, control_flow: ControlFlow) -> bool {
+        match control_flow {
+            ControlFlow::Poll => drain_pending(),
+            ControlFlow::Wait | ControlFlow::Exit => {
+                let mut msg = MSG::default();
+                // SAFETY: `msg` is a valid out-parameter for the duration of
+                // the call.
+                if !unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {
+                    return false;
+                }
+                dispatch(&msg);
+                true
+            }
+            ControlFlow::WaitUntil(deadline) => self.wait_until(deadline),
+        }
+    }
+
+    /// Blocks until either a message arrives or `deadline` passes, using a
+    /// one-shot timer to wake `GetMessageW` at the deadline, then drains
+    /// whatever else is already queued.
+    fn wait_until(&self, deadline: Instant) -> bool {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining == Duration::ZERO {
+            return drain_pending();
+        }
+
+        let elapse_ms = remaining.as_millis().clamp(1, u32::MAX as u128) as u32;
+        // SAFETY: a `None` window creates a thread-level timer that posts
+        // `WM_TIMER` to this thread's message queue rather than any window.
+        let timer_id = unsafe { SetTimer(None, 0, elapse_ms, None) };
+
+        let mut msg = MSG::default();
+        // SAFETY: `msg` is a valid out-parameter for the duration of the
+        // call.
+        let got = unsafe { GetMessageW(&mut msg, None, 0, 0) };
+
+        // SAFETY: `timer_id` was created on this thread immediately above.
+        unsafe {
+            let _ = KillTimer(None, timer_id);
+        }
+
+        if !got.as_bool() {
+            return false;
+        }
+        if msg.message != WM_TIMER || msg.wParam.0 != timer_id {
+            dispatch(&msg);
+        }
+
+        drain_pending()
+    }
+
+    /// Translates the window's pending state into typed events.
+    fn drain_events(&mut self, window: &Window) -> Vec<Event> {
+        let mut events = Vec::new();
+
+        if window.is_requesting_close() && !self.close_reported {
+            self.close_reported = true;
+            events.push(Event::CloseRequested);
+        }
+
+        if window.is_requesting_shutdown() && !self.shutdown_reported {
+            self.shutdown_reported = true;
+            events.push(Event::ShutdownRequested);
+        }
+
+        let paint_generation = window.paint_generation();
+        if paint_generation != self.last_paint_generation {
+            self.last_paint_generation = paint_generation;
+            events.push(Event::Paint);
+        }
+
+        if let Some(size) = window.take_resize() {
+            events.push(Event::Resized(size));
+        }
+
+        if let Some(state) = window.take_state_change() {
+            events.push(Event::StateChanged(state));
+        }
+
+        if let Some(entering) = window.take_size_move_change() {
+            events.push(if entering {
+                Event::EnterSizeMove
+            } else {
+                Event::ExitSizeMove
+            });
+        }
+
+        let dpi_generation = window.dpi_generation();
+        if dpi_generation != self.last_dpi_generation {
+            self.last_dpi_generation = dpi_generation;
+            events.push(Event::DpiChanged(window.dpi()));
+        }
+
+        let theme_change_generation = window.theme_change_generation();
+        if theme_change_generation != self.last_theme_change_generation {
+            self.last_theme_change_generation = theme_change_generation;
+            events.push(Event::ThemeChanged);
+        }
+
+        events.extend(
+            window
+                .drain_key_transitions()
+                .into_iter()
+                .map(|(code, pressed)| Event::Key { code, pressed }),
+        );
+
+        let focused = window.is_focused();
+        if focused != self.last_focused {
+            self.last_focused = focused;
+            events.push(Event::FocusChanged(focused));
+        }
+
+        events
+    }
+}
+
+/// Dispatches a single already-retrieved message to its window procedure.
+fn dispatch(msg: &MSG) {
+    // SAFETY: `msg` was just filled in by `GetMessageW`/`PeekMessageW`.
+    unsafe {
+        let _ = TranslateMessage(msg);
+        DispatchMessageW(msg);
+    }
+}
+
+/// Drains every message currently queued without blocking. Returns `false`
+/// if a `WM_QUIT` was received.
+fn drain_pending() -> bool {
+    let mut msg = MSG::default();
+    // SAFETY: `msg` is a valid out-parameter for the duration of each call.
+    while unsafe { PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE) }.as_bool() {
+        if msg.message == WM_QUIT {
+            return false;
+        }
+        dispatch(&msg);
+    }
+    true
+}
@@ -0,0 +1,127 @@
+//! A feature-gated gamepad backend built on the WinRT
+//! `Windows.Gaming.Input` API rather than XInput, so modern controllers
+//! (DualSense, newer Xbox pads, ...) work too: more than four
+//! simultaneous pads, and per-trigger "impulse" rumble motors that XInput
+//! can't address.
+
+use windows::Gaming::Input::{Gamepad as WinRtGamepad, GamepadButtons, GamepadVibration};
+
+use crate::error::{Error, Result};
+
+/// A button on a [`Gamepad`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    Menu,
+    View,
+    A,
+    B,
+    X,
+    Y,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    LeftShoulder,
+    RightShoulder,
+    LeftThumbstick,
+    RightThumbstick,
+    Paddle1,
+    Paddle2,
+    Paddle3,
+    Paddle4,
+}
+
+impl GamepadButton {
+    fn bits(self) -> GamepadButtons {
+        match self {
+            Self::Menu => GamepadButtons::Menu,
+            Self::View => GamepadButtons::View,
+            Self::A => GamepadButtons::A,
+            Self::B => GamepadButtons::B,
+            Self::X => GamepadButtons::X,
+            Self::Y => GamepadButtons::Y,
+            Self::DPadUp => GamepadButtons::DPadUp,
+            Self::DPadDown => GamepadButtons::DPadDown,
+            Self::DPadLeft => GamepadButtons::DPadLeft,
+            Self::DPadRight => GamepadButtons::DPadRight,
+            Self::LeftShoulder => GamepadButtons::LeftShoulder,
+            Self::RightShoulder => GamepadButtons::RightShoulder,
+            Self::LeftThumbstick => GamepadButtons::LeftThumbstick,
+            Self::RightThumbstick => GamepadButtons::RightThumbstick,
+            Self::Paddle1 => GamepadButtons::Paddle1,
+            Self::Paddle2 => GamepadButtons::Paddle2,
+            Self::Paddle3 => GamepadButtons::Paddle3,
+            Self::Paddle4 => GamepadButtons::Paddle4,
+        }
+    }
+}
+
+/// A single polled snapshot of a [`Gamepad`]'s digital and analog state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GamepadState {
+    buttons: GamepadButtons,
+    /// The left analog trigger, from `0.0` (released) to `1.0` (fully
+    /// pulled).
+    pub left_trigger: f64,
+    /// The right analog trigger, from `0.0` (released) to `1.0` (fully
+    /// pulled).
+    pub right_trigger: f64,
+    /// The left thumbstick's `(x, y)` position, each axis from `-1.0` to
+    /// `1.0`.
+    pub left_stick: (f64, f64),
+    /// The right thumbstick's `(x, y)` position, each axis from `-1.0` to
+    /// `1.0`.
+    pub right_stick: (f64, f64),
+}
+
+impl GamepadState {
+    /// Returns `true` if `button` is currently held down.
+    pub fn is_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons.contains(button.bits())
+    }
+}
+
+/// A controller connected via `Windows.Gaming.Input`.
+#[derive(Debug, Clone)]
+pub struct Gamepad(WinRtGamepad);
+
+impl Gamepad {
+    /// Every gamepad currently connected to the system.
+    pub fn all() -> Result<Vec<Self>> {
+        let gamepads = WinRtGamepad::Gamepads().map_err(Error::from)?;
+        Ok(gamepads.into_iter().map(Self).collect())
+    }
+
+    /// Polls this gamepad's current digital and analog state.
+    pub fn state(&self) -> Result<GamepadState> {
+        let reading = self.0.GetCurrentReading().map_err(Error::from)?;
+        Ok(GamepadState {
+            buttons: reading.Buttons,
+            left_trigger: reading.LeftTrigger,
+            right_trigger: reading.RightTrigger,
+            left_stick: (reading.LeftThumbstickX, reading.LeftThumbstickY),
+            right_stick: (reading.RightThumbstickX, reading.RightThumbstickY),
+        })
+    }
+
+    /// Sets the rumble motors. `left_trigger`/`right_trigger` drive the
+    /// per-trigger impulse motors modern controllers add alongside the
+    /// two main `left_motor`/`right_motor` motors. Every value ranges from
+    /// `0.0` (off) to `1.0` (full strength).
+    pub fn set_vibration(
+        &self,
+        left_motor: f64,
+        right_motor: f64,
+        left_trigger: f64,
+        right_trigger: f64,
+    ) -> Result<()> {
+        self.0
+            .SetVibration(GamepadVibration {
+                LeftMotor: left_motor,
+                RightMotor: right_motor,
+                LeftTrigger: left_trigger,
+                RightTrigger: right_trigger,
+            })
+            .map_err(Error::from)
+    }
+}
@@ -0,0 +1,93 @@
+//! Small, dependency-free geometry primitives used throughout the public
+//! API in place of raw Win32 `RECT`/`POINT`/`SIZE` structures.
+
+use windows::Win32::Foundation::{POINT, RECT, SIZE};
+
+/// A 2D point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Point2D<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2D<T> {
+    /// Creates a new point from its components.
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<POINT> for Point2D<i32> {
+    fn from(p: POINT) -> Self {
+        Self::new(p.x, p.y)
+    }
+}
+
+impl From<Point2D<i32>> for POINT {
+    fn from(p: Point2D<i32>) -> Self {
+        POINT { x: p.x, y: p.y }
+    }
+}
+
+/// A 2D size (width and height).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size2D<T> {
+    pub width: T,
+    pub height: T,
+}
+
+impl<T> Size2D<T> {
+    /// Creates a new size from its components.
+    pub const fn new(width: T, height: T) -> Self {
+        Self { width, height }
+    }
+}
+
+impl From<SIZE> for Size2D<i32> {
+    fn from(s: SIZE) -> Self {
+        Self::new(s.cx, s.cy)
+    }
+}
+
+impl From<Size2D<i32>> for SIZE {
+    fn from(s: Size2D<i32>) -> Self {
+        SIZE {
+            cx: s.width,
+            cy: s.height,
+        }
+    }
+}
+
+/// An axis-aligned rectangle, expressed as an origin and a size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect2D<T> {
+    pub origin: Point2D<T>,
+    pub size: Size2D<T>,
+}
+
+impl<T: Copy> Rect2D<T> {
+    /// Creates a new rectangle from its origin and size.
+    pub const fn new(origin: Point2D<T>, size: Size2D<T>) -> Self {
+        Self { origin, size }
+    }
+}
+
+impl From<RECT> for Rect2D<i32> {
+    fn from(r: RECT) -> Self {
+        Self::new(
+            Point2D::new(r.left, r.top),
+            Size2D::new(r.right - r.left, r.bottom - r.top),
+        )
+    }
+}
+
+impl From<Rect2D<i32>> for RECT {
+    fn from(r: Rect2D<i32>) -> Self {
+        RECT {
+            left: r.origin.x,
+            top: r.origin.y,
+            right: r.origin.x + r.size.width,
+            bottom: r.origin.y + r.size.height,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Two-finger pinch/rotate/pan gesture recognition built on top of
+//! [`crate::touch::TouchContact`] streams. Win32's own `WM_GESTURE`
+//! message only reports pre-digested zoom/rotate/pan deltas for the
+//! legacy touch stack and doesn't coexist well with `WM_POINTER`, so
+//! gestures are derived here directly from raw touch contacts instead.
+
+use crate::geometry::Point2D;
+use crate::touch::{TouchContact, TouchPhase};
+
+/// A two-finger gesture delta, computed between two consecutive frames in
+/// which both fingers were down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gesture {
+    /// The multiplicative change in distance between the two fingers since
+    /// the previous frame; `1.0` means no change, `>1.0` is a pinch-out
+    /// (zoom in), `<1.0` is a pinch-in (zoom out).
+    pub scale: f64,
+    /// The change in angle between the two fingers since the previous
+    /// frame, in radians, measured in client coordinates (y pointing down),
+    /// so a positive value is a clockwise turn on screen.
+    pub rotation: f64,
+    /// The movement of the midpoint between the two fingers since the
+    /// previous frame, in client pixels.
+    pub pan: Point2D<f64>,
+}
+
+/// Tracks up to two simultaneous touch contacts and derives [`Gesture`]
+/// deltas from how they move relative to each other.
+#[derive(Debug, Default)]
+pub struct GestureRecognizer {
+    contacts: [Option<TouchContact>; 2],
+}
+
+impl GestureRecognizer {
+    /// Creates a recognizer tracking no contacts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a touch contact into the recognizer, returning a [`Gesture`]
+    /// only when both fingers were already down before this update and
+    /// still are after it; the update that first brings the second finger
+    /// down returns `None`.
+    pub fn update(&mut self, contact: TouchContact) -> Option<Gesture> {
+        let slot = self.slot_for(contact.id);
+
+        if contact.phase == TouchPhase::Up {
+            self.contacts[slot] = None;
+            return None;
+        }
+
+        let previous = self.contacts;
+        self.contacts[slot] = Some(contact);
+
+        match (previous[0], previous[1], self.contacts[0], self.contacts[1]) {
+            (Some(a0), Some(b0), Some(a1), Some(b1)) => Some(gesture_delta(a0, b0, a1, b1)),
+            _ => None,
+        }
+    }
+
+    /// Finds the slot already tracking `id`, or the first free slot if
+    /// this is a new contact. Falls back to slot `0` if both slots are
+    /// already taken by other fingers, so a third finger always replaces
+    /// whichever contact occupies slot `0`.
+    fn slot_for(&self, id: u32) -> usize {
+        self.contacts
+            .iter()
+            .position(|c| c.is_some_and(|c| c.id == id))
+            .or_else(|| self.contacts.iter().position(|c| c.is_none()))
+            .unwrap_or(0)
+    }
+}
+
+/// Computes the [`Gesture`] delta between two fingers' previous positions
+/// (`a0`, `b0`) and current positions (`a1`, `b1`).
+fn gesture_delta(
+    a0: TouchContact,
+    b0: TouchContact,
+    a1: TouchContact,
+    b1: TouchContact,
+) -> Gesture {
+    let distance = |p: Point2D<i32>, q: Point2D<i32>| {
+        (((q.x - p.x) as f64).powi(2) + ((q.y - p.y) as f64).powi(2)).sqrt()
+    };
+    let angle = |p: Point2D<i32>, q: Point2D<i32>| ((q.y - p.y) as f64).atan2((q.x - p.x) as f64);
+    let midpoint = |p: Point2D<i32>, q: Point2D<i32>| {
+        Point2D::new(
+            (p.x as f64 + q.x as f64) / 2.0,
+            (p.y as f64 + q.y as f64) / 2.0,
+        )
+    };
+
+    let previous_distance = distance(a0.position, b0.position);
+    let scale = if previous_distance > 0.0 {
+        distance(a1.position, b1.position) / previous_distance
+    } else {
+        1.0
+    };
+
+    let previous_midpoint = midpoint(a0.position, b0.position);
+    let midpoint = midpoint(a1.position, b1.position);
+
+    Gesture {
+        scale,
+        rotation: angle(a1.position, b1.position) - angle(a0.position, b0.position),
+        pan: Point2D::new(
+            midpoint.x - previous_midpoint.x,
+            midpoint.y - previous_midpoint.y,
+        ),
+    }
+}
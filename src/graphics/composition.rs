@@ -0,0 +1,75 @@
+//! Tear-free, transparent rendering via DirectComposition, bound to a
+//! [`Window`] created with
+//! [`crate::window::Builder::with_composition_target`].
+
+use windows::Win32::Graphics::DirectComposition::{
+    DCompositionCreateDevice, IDCompositionDevice, IDCompositionTarget, IDCompositionVisual,
+};
+use windows::Win32::Graphics::Dxgi::{IDXGIDevice, IDXGISwapChain1};
+
+use crate::error::{Error, Result};
+use crate::window::Window;
+
+/// A DirectComposition device, creating the targets and visuals that bind
+/// a [`Window`]'s content to the desktop compositor.
+pub struct Compositor {
+    device: IDCompositionDevice,
+}
+
+impl Compositor {
+    /// Creates a compositor backed by `dxgi_device`, typically the device
+    /// behind a [`crate::graphics::dxgi::Swapchain`].
+    pub fn new(dxgi_device: &IDXGIDevice) -> Result<Self> {
+        // SAFETY: `dxgi_device` is a live COM object.
+        let device: IDCompositionDevice =
+            unsafe { DCompositionCreateDevice(dxgi_device) }.map_err(Error::from)?;
+        Ok(Self { device })
+    }
+
+    /// Binds this compositor's visual tree to `window`, which must have
+    /// been created with
+    /// [`crate::window::Builder::with_composition_target`].
+    pub fn create_target(&self, window: &Window) -> Result<Target> {
+        // SAFETY: `window.hwnd()` is a valid, live window.
+        let target =
+            unsafe { self.device.CreateTargetForHwnd(window.hwnd(), true) }.map_err(Error::from)?;
+        Ok(Target(target))
+    }
+
+    /// Creates a new, empty visual for this compositor's tree.
+    pub fn create_visual(&self) -> Result<Visual> {
+        // SAFETY: `self.device` is a live COM object.
+        let visual = unsafe { self.device.CreateVisual() }.map_err(Error::from)?;
+        Ok(Visual(visual))
+    }
+
+    /// Submits all pending visual tree changes for composition.
+    pub fn commit(&self) -> Result<()> {
+        // SAFETY: `self.device` is a live COM object.
+        unsafe { self.device.Commit() }.map_err(Error::from)
+    }
+}
+
+/// The binding between a [`Compositor`]'s visual tree and a window's HWND,
+/// created via [`Compositor::create_target`].
+pub struct Target(IDCompositionTarget);
+
+impl Target {
+    /// Sets `visual` as the root of this target's visual tree.
+    pub fn set_root(&self, visual: &Visual) -> Result<()> {
+        // SAFETY: `self.0` and `visual.0` are both live COM objects.
+        unsafe { self.0.SetRoot(&visual.0) }.map_err(Error::from)
+    }
+}
+
+/// A node in a [`Compositor`]'s visual tree, created via
+/// [`Compositor::create_visual`].
+pub struct Visual(IDCompositionVisual);
+
+impl Visual {
+    /// Sets `swapchain` as this visual's rendered content.
+    pub fn set_content(&self, swapchain: &IDXGISwapChain1) -> Result<()> {
+        // SAFETY: `self.0` and `swapchain` are both live COM objects.
+        unsafe { self.0.SetContent(swapchain) }.map_err(Error::from)
+    }
+}
@@ -0,0 +1,138 @@
+//! Direct2D rendering bound to a [`Window`]'s client area.
+
+use windows::Win32::Foundation::{D2DERR_RECREATE_TARGET, HWND};
+use windows::Win32::Graphics::Direct2D::Common::{D2D1_COLOR_F, D2D1_PIXEL_FORMAT, D2D_SIZE_U};
+use windows::Win32::Graphics::Direct2D::{
+    D2D1CreateFactory, ID2D1Factory, ID2D1HwndRenderTarget, ID2D1RenderTarget,
+    D2D1_FACTORY_TYPE_SINGLE_THREADED, D2D1_HWND_RENDER_TARGET_PROPERTIES,
+    D2D1_PRESENT_OPTIONS_NONE, D2D1_RENDER_TARGET_PROPERTIES, D2D1_RENDER_TARGET_TYPE_DEFAULT,
+};
+
+use crate::error::{Error, Result};
+use crate::geometry::Size2D;
+use crate::window::Window;
+
+/// A Direct2D render target bound to a [`Window`]'s client area.
+///
+/// Handles resizing and transparently recreates the target after device
+/// loss; apps only need to call [`Context::begin_draw`] once per
+/// `WM_PAINT` and issue draw calls against the returned [`DrawGuard`].
+pub struct Context {
+    hwnd: HWND,
+    factory: ID2D1Factory,
+    target: ID2D1HwndRenderTarget,
+    /// Set when a previous `EndDraw` reported `D2DERR_RECREATE_TARGET`, so
+    /// the next [`Context::begin_draw`] recreates `target` before use.
+    lost: bool,
+}
+
+impl Context {
+    /// Creates a Direct2D render target sized to `window`'s current client
+    /// area.
+    pub fn new(window: &Window) -> Result<Self> {
+        // SAFETY: `D2D1CreateFactory` only writes through the out-pointer
+        // that `windows-rs` supplies for the requested `ID2D1Factory`.
+        let factory: ID2D1Factory =
+            unsafe { D2D1CreateFactory(D2D1_FACTORY_TYPE_SINGLE_THREADED, None) }
+                .map_err(Error::from)?;
+
+        let hwnd = window.hwnd();
+        let target = create_target(&factory, hwnd, window.inner_size()?)?;
+
+        Ok(Self {
+            hwnd,
+            factory,
+            target,
+            lost: false,
+        })
+    }
+
+    /// Resizes the render target to match the window's new client size,
+    /// e.g. in response to [`crate::event_loop::Event::Resized`].
+    pub fn resize(&self, size: Size2D<i32>) -> Result<()> {
+        let size = to_pixel_size(size);
+        // SAFETY: `self.target` is a live `ID2D1HwndRenderTarget`.
+        unsafe { self.target.Resize(&size) }.map_err(Error::from)
+    }
+
+    /// Begins a frame, returning a guard that ends it on drop. If the
+    /// target was lost since the previous frame (e.g. a display driver
+    /// reset), it is transparently recreated at `size` before drawing
+    /// begins.
+    pub fn begin_draw(&mut self, size: Size2D<i32>) -> Result<DrawGuard<'_>> {
+        if self.lost {
+            self.target = create_target(&self.factory, self.hwnd, size)?;
+            self.lost = false;
+        }
+
+        // SAFETY: `self.target` is a live `ID2D1HwndRenderTarget`.
+        unsafe { self.target.BeginDraw() };
+
+        Ok(DrawGuard { context: self })
+    }
+
+    /// The underlying render target, for issuing draw calls directly.
+    pub fn target(&self) -> &ID2D1RenderTarget {
+        &self.target
+    }
+}
+
+/// An in-progress Direct2D frame, started by [`Context::begin_draw`].
+/// `EndDraw` is called automatically on drop; if it reports device loss,
+/// the next [`Context::begin_draw`] recreates the render target.
+pub struct DrawGuard<'a> {
+    context: &'a mut Context,
+}
+
+impl DrawGuard<'_> {
+    /// Clears the render target to `color`.
+    pub fn clear(&self, color: D2D1_COLOR_F) {
+        // SAFETY: called between `BeginDraw` and `EndDraw`.
+        unsafe { self.context.target.Clear(Some(&color)) };
+    }
+
+    /// The underlying render target, for issuing draw calls directly.
+    pub fn target(&self) -> &ID2D1RenderTarget {
+        &self.context.target
+    }
+}
+
+impl Drop for DrawGuard<'_> {
+    fn drop(&mut self) {
+        // SAFETY: a matching `BeginDraw` was called in `Context::begin_draw`.
+        if let Err(err) = unsafe { self.context.target.EndDraw(None, None) } {
+            self.context.lost = err.code() == D2DERR_RECREATE_TARGET;
+        }
+    }
+}
+
+/// Creates a new `ID2D1HwndRenderTarget` for `hwnd` at `size`.
+fn create_target(
+    factory: &ID2D1Factory,
+    hwnd: HWND,
+    size: Size2D<i32>,
+) -> Result<ID2D1HwndRenderTarget> {
+    let render_target_properties = D2D1_RENDER_TARGET_PROPERTIES {
+        r#type: D2D1_RENDER_TARGET_TYPE_DEFAULT,
+        pixelFormat: D2D1_PIXEL_FORMAT::default(),
+        ..Default::default()
+    };
+    let hwnd_properties = D2D1_HWND_RENDER_TARGET_PROPERTIES {
+        hwnd,
+        pixelSize: to_pixel_size(size),
+        presentOptions: D2D1_PRESENT_OPTIONS_NONE,
+    };
+
+    // SAFETY: both property structs are fully initialized above.
+    unsafe { factory.CreateHwndRenderTarget(&render_target_properties, &hwnd_properties) }
+        .map_err(Error::from)
+}
+
+/// Converts a logical [`Size2D`] into the `D2D_SIZE_U` Direct2D expects,
+/// clamping to a minimum of 1x1 since a zero-sized render target is invalid.
+fn to_pixel_size(size: Size2D<i32>) -> D2D_SIZE_U {
+    D2D_SIZE_U {
+        width: size.width.max(1) as u32,
+        height: size.height.max(1) as u32,
+    }
+}
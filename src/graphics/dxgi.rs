@@ -0,0 +1,146 @@
+//! A flip-model DXGI swapchain bound to a [`Window`], with a D3D11 device
+//! and immediate context created to drive it.
+
+use windows::core::Interface;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+    D3D11_SDK_VERSION,
+};
+use windows::Win32::Graphics::Dxgi::Common::{
+    DXGI_ALPHA_MODE_UNSPECIFIED, DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_UNKNOWN, DXGI_SAMPLE_DESC,
+};
+use windows::Win32::Graphics::Dxgi::{
+    IDXGIDevice, IDXGIFactory2, IDXGISwapChain1, DXGI_PRESENT, DXGI_SCALING_STRETCH,
+    DXGI_SWAP_CHAIN_DESC1, DXGI_SWAP_CHAIN_FLAG, DXGI_SWAP_EFFECT_FLIP_DISCARD,
+    DXGI_USAGE_RENDER_TARGET_OUTPUT,
+};
+
+use crate::error::{Error, Result};
+use crate::geometry::Size2D;
+use crate::window::Window;
+
+/// A flip-model DXGI swapchain bound to a [`Window`]'s client area, with
+/// the D3D11 device and immediate context that created it.
+pub struct Swapchain {
+    device: ID3D11Device,
+    context: ID3D11DeviceContext,
+    swapchain: IDXGISwapChain1,
+}
+
+impl Swapchain {
+    /// Creates a hardware D3D11 device and a flip-model swapchain sized to
+    /// `window`'s current client area.
+    pub fn new(window: &Window) -> Result<Self> {
+        let mut device = None;
+        let mut context = None;
+        // SAFETY: `device` and `context` are valid out-parameters; no
+        // adapter, software rasterizer, or feature level list is forced,
+        // so the default hardware adapter and its best feature level are
+        // used.
+        unsafe {
+            D3D11CreateDevice(
+                None,
+                D3D_DRIVER_TYPE_HARDWARE,
+                HMODULE::default(),
+                D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                None,
+                D3D11_SDK_VERSION,
+                Some(&mut device),
+                None,
+                Some(&mut context),
+            )
+        }
+        .map_err(Error::from)?;
+
+        let device = device.ok_or_else(|| Error::from(windows::core::Error::from_win32()))?;
+        let context = context.ok_or_else(|| Error::from(windows::core::Error::from_win32()))?;
+
+        // The swapchain must be created through the same adapter's DXGI
+        // factory that backs the device, so it can't just call
+        // `CreateDXGIFactory2` directly.
+        let dxgi_device: IDXGIDevice = device.cast().map_err(Error::from)?;
+        // SAFETY: `dxgi_device` is a live COM object.
+        let adapter = unsafe { dxgi_device.GetAdapter() }.map_err(Error::from)?;
+        // SAFETY: `adapter` is a live COM object.
+        let factory: IDXGIFactory2 = unsafe { adapter.GetParent() }.map_err(Error::from)?;
+
+        let size = window.inner_size()?;
+        let desc = DXGI_SWAP_CHAIN_DESC1 {
+            Width: size.width.max(1) as u32,
+            Height: size.height.max(1) as u32,
+            Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+            SampleDesc: DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            BufferUsage: DXGI_USAGE_RENDER_TARGET_OUTPUT,
+            BufferCount: 2,
+            Scaling: DXGI_SCALING_STRETCH,
+            SwapEffect: DXGI_SWAP_EFFECT_FLIP_DISCARD,
+            AlphaMode: DXGI_ALPHA_MODE_UNSPECIFIED,
+            ..Default::default()
+        };
+
+        // SAFETY: `desc` is fully initialized and `window.hwnd()` is a
+        // valid, live window.
+        let swapchain =
+            unsafe { factory.CreateSwapChainForHwnd(&device, window.hwnd(), &desc, None, None) }
+                .map_err(Error::from)?;
+
+        Ok(Self {
+            device,
+            context,
+            swapchain,
+        })
+    }
+
+    /// The D3D11 device that owns the swapchain.
+    pub fn device(&self) -> &ID3D11Device {
+        &self.device
+    }
+
+    /// The D3D11 immediate context created alongside [`Swapchain::device`].
+    pub fn context(&self) -> &ID3D11DeviceContext {
+        &self.context
+    }
+
+    /// Gets the swapchain's back buffer as the requested D3D11 resource
+    /// interface, e.g. `ID3D11Texture2D`.
+    pub fn back_buffer<T: Interface>(&self) -> Result<T> {
+        // SAFETY: `self.swapchain` is a live `IDXGISwapChain1`.
+        unsafe { self.swapchain.GetBuffer(0) }.map_err(Error::from)
+    }
+
+    /// Resizes the swapchain's buffers to match the window's new client
+    /// size, e.g. in response to [`crate::event_loop::Event::Resized`].
+    /// Callers must release any outstanding back-buffer references (e.g.
+    /// render target views) before calling this.
+    pub fn resize(&self, size: Size2D<i32>) -> Result<()> {
+        // SAFETY: `self.swapchain` is a live `IDXGISwapChain1`.
+        unsafe {
+            self.swapchain.ResizeBuffers(
+                0,
+                size.width.max(1) as u32,
+                size.height.max(1) as u32,
+                DXGI_FORMAT_UNKNOWN,
+                DXGI_SWAP_CHAIN_FLAG::default(),
+            )
+        }
+        .map_err(Error::from)
+    }
+
+    /// Presents the current back buffer, blocking for vertical sync if
+    /// `vsync` is `true`.
+    pub fn present(&self, vsync: bool) -> Result<()> {
+        let sync_interval = u32::from(vsync);
+        // SAFETY: `self.swapchain` is a live `IDXGISwapChain1`.
+        unsafe {
+            self.swapchain
+                .Present(sync_interval, DXGI_PRESENT::default())
+        }
+        .ok()
+        .map_err(Error::from)
+    }
+}
@@ -0,0 +1,118 @@
+//! A CPU-rendered RGBA framebuffer blitted to a [`Window`] via GDI, so
+//! simple apps and tests can render without any GPU API.
+
+use windows::Win32::Graphics::Gdi::{
+    GetDC, ReleaseDC, StretchDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS, SRCCOPY,
+};
+
+use crate::error::{Error, Result};
+use crate::geometry::Size2D;
+use crate::window::Window;
+
+/// A CPU-side pixel buffer that can be blitted to a [`Window`]'s client
+/// area on demand, e.g. in response to [`Window::is_requesting_paint`].
+pub struct Framebuffer {
+    size: Size2D<i32>,
+    /// Row-major, top-to-bottom; each pixel packed as `0x00RRGGBB`.
+    pixels: Vec<u32>,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer of `size` pixels, initially all black.
+    pub fn new(size: Size2D<i32>) -> Self {
+        Self {
+            size,
+            pixels: vec![0; pixel_count(size)],
+        }
+    }
+
+    /// The framebuffer's current size, in pixels.
+    pub fn size(&self) -> Size2D<i32> {
+        self.size
+    }
+
+    /// Resizes the framebuffer, e.g. in response to
+    /// [`crate::event_loop::Event::Resized`]. Existing pixel data is
+    /// discarded.
+    pub fn resize(&mut self, size: Size2D<i32>) {
+        self.size = size;
+        self.pixels.clear();
+        self.pixels.resize(pixel_count(size), 0);
+    }
+
+    /// The raw pixel buffer, row-major top-to-bottom, each pixel packed as
+    /// `0x00RRGGBB`.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// Mutable access to [`Framebuffer::pixels`].
+    pub fn pixels_mut(&mut self) -> &mut [u32] {
+        &mut self.pixels
+    }
+
+    /// Blits the framebuffer's current contents to `window`'s client area.
+    pub fn present(&self, window: &Window) -> Result<()> {
+        let hwnd = window.hwnd();
+
+        // SAFETY: `Some(hwnd)` requests the client-area device context for
+        // a valid, live window.
+        let hdc = unsafe { GetDC(Some(hwnd)) };
+        if hdc.is_invalid() {
+            return Err(Error::from(windows::core::Error::from_win32()));
+        }
+
+        let info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: self.size.width,
+                // Negative height selects a top-down DIB, matching the
+                // row order of `self.pixels`.
+                biHeight: -self.size.height,
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // SAFETY: `self.pixels` holds exactly `self.size.width *
+        // self.size.height` pixels matching `info`'s dimensions and pixel
+        // format.
+        let lines_copied = unsafe {
+            StretchDIBits(
+                hdc,
+                0,
+                0,
+                self.size.width,
+                self.size.height,
+                0,
+                0,
+                self.size.width,
+                self.size.height,
+                Some(self.pixels.as_ptr().cast()),
+                &info,
+                DIB_RGB_COLORS,
+                SRCCOPY,
+            )
+        };
+
+        // SAFETY: `hdc` was acquired from `GetDC` above and is released
+        // exactly once here.
+        unsafe {
+            ReleaseDC(Some(hwnd), hdc);
+        }
+
+        if lines_copied == 0 {
+            return Err(Error::from(windows::core::Error::from_win32()));
+        }
+        Ok(())
+    }
+}
+
+/// The number of pixels in a framebuffer of `size`, clamping negative
+/// dimensions to zero.
+fn pixel_count(size: Size2D<i32>) -> usize {
+    size.width.max(0) as usize * size.height.max(0) as usize
+}
@@ -0,0 +1,12 @@
+//! Optional rendering backends bound to a [`crate::window::Window`]'s
+//! client area, each gated behind its own Cargo feature so apps only pull
+//! in the graphics API they actually use.
+
+#[cfg(feature = "composition")]
+pub mod composition;
+#[cfg(feature = "d2d")]
+pub mod d2d;
+#[cfg(feature = "dxgi")]
+pub mod dxgi;
+#[cfg(feature = "framebuffer")]
+pub mod framebuffer;
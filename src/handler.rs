@@ -0,0 +1,71 @@
+//! A trait-based alternative to polling `Window::is_requesting_*`, for apps
+//! that want to react to window events as they occur.
+
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+
+use crate::keyboard::KeyCode;
+use crate::window::WindowHandle;
+
+/// Reacts to events for a single window, supplied via
+/// [`crate::window::Builder::with_handler`].
+///
+/// Every method has a default no-op implementation, so a handler only
+/// needs to override what it cares about.
+pub trait WindowHandler {
+    /// Called when the window needs to be repainted.
+    fn on_paint(&mut self, _window: WindowHandle<'_>) {}
+
+    /// Called when the user requests that the window be closed. Returning
+    /// [`CloseResponse::Deny`] vetoes the close: the window stays open and
+    /// neither [`Window::is_requesting_close`](crate::window::Window::is_requesting_close)
+    /// nor [`crate::event_loop::Event::CloseRequested`] fire, so an
+    /// "unsaved changes" prompt can cancel `WM_CLOSE` cleanly instead of
+    /// having to undo the request flag afterwards.
+    fn on_close(&mut self, _window: WindowHandle<'_>) -> CloseResponse {
+        CloseResponse::Allow
+    }
+
+    /// Called when the OS requests that the session end (shutdown, restart,
+    /// or log off), via `WM_QUERYENDSESSION`. Runs synchronously before the
+    /// session is allowed to proceed, so it's the last reliable chance to
+    /// save state; call
+    /// [`Window::block_shutdown`](crate::window::Window::block_shutdown) to
+    /// hold the session open briefly if saving needs more time. Returning
+    /// [`CloseResponse::Deny`] vetoes the shutdown, the same as
+    /// [`WindowHandler::on_close`] does for `WM_CLOSE`.
+    fn on_shutdown_requested(&mut self, _window: WindowHandle<'_>) -> CloseResponse {
+        CloseResponse::Allow
+    }
+
+    /// Called when a key is pressed or released.
+    fn on_key(&mut self, _window: WindowHandle<'_>, _code: KeyCode, _pressed: bool) {}
+
+    /// Called when a child control (see [`crate::controls`]) sends a
+    /// `WM_COMMAND` notification, e.g. `BN_CLICKED` for a button or
+    /// `EN_CHANGE` for an edit control. `id` is the control's ID and
+    /// `notification` the notification code from the message's high word.
+    fn on_control_event(&mut self, _window: WindowHandle<'_>, _id: u32, _notification: u32) {}
+
+    /// Called for every raw message the window receives, before any of the
+    /// more specific handler methods above, for apps that need to observe
+    /// messages skylight doesn't otherwise expose.
+    fn on_raw_message(
+        &mut self,
+        _window: WindowHandle<'_>,
+        _msg: u32,
+        _wparam: WPARAM,
+        _lparam: LPARAM,
+    ) {
+    }
+}
+
+/// How a [`WindowHandler::on_close`] implementation responds to a close
+/// request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CloseResponse {
+    /// Allow the close to proceed.
+    #[default]
+    Allow,
+    /// Veto the close: the window stays open and the request is dropped.
+    Deny,
+}
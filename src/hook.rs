@@ -0,0 +1,182 @@
+//! An opt-in, system-wide low-level keyboard hook (`WH_KEYBOARD_LL`), for
+//! launcher- and overlay-style apps that need to observe or swallow
+//! keystrokes even when no window of theirs has focus.
+//!
+//! `WH_KEYBOARD_LL` hooks are delivered by posting to the message queue of
+//! whichever thread installed them, so [`KeyboardHook::install`] spawns a
+//! dedicated thread with its own message loop and hands back a channel of
+//! [`HookEvent`]s. Swallowing a key has to be decided synchronously, before
+//! the hook procedure returns, so it's driven by a callback that runs on
+//! that dedicated thread rather than by a reply sent back down a channel.
+
+use std::cell::RefCell;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+use windows::Win32::Foundation::{LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, DispatchMessageW, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
+    TranslateMessage, UnhookWindowsHookEx, HC_ACTION, KBDLLHOOKSTRUCT, MSG, WH_KEYBOARD_LL,
+    WM_KEYDOWN, WM_QUIT, WM_SYSKEYDOWN,
+};
+
+use crate::error::{Error, Result};
+use crate::keyboard::KeyCode;
+
+/// A keystroke observed by a [`KeyboardHook`], before it reaches any
+/// window's own message queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HookEvent {
+    /// The key that transitioned, if it maps to a known [`KeyCode`].
+    pub code: Option<KeyCode>,
+    /// `true` if the key was pressed, `false` if released.
+    pub pressed: bool,
+    /// The hardware scan code reported by the driver.
+    pub scan_code: u32,
+}
+
+/// Whether a [`KeyboardHook`]'s callback should let a keystroke continue on
+/// to its normal destination, or swallow it so nothing downstream — not
+/// even this process's own windows — ever sees it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookAction {
+    /// Let the keystroke continue through the system as normal.
+    PassThrough,
+    /// Swallow the keystroke.
+    Swallow,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<HookState>> = const { RefCell::new(None) };
+}
+
+struct HookState {
+    callback: Box<dyn FnMut(&HookEvent) -> HookAction>,
+    events: Sender<HookEvent>,
+}
+
+/// A system-wide `WH_KEYBOARD_LL` hook running on its own dedicated
+/// thread. Dropping it unhooks and stops that thread.
+#[derive(Debug)]
+pub struct KeyboardHook {
+    thread: Option<JoinHandle<()>>,
+    thread_id: u32,
+    events: Receiver<HookEvent>,
+}
+
+impl KeyboardHook {
+    /// Installs a low-level keyboard hook, spawning the dedicated thread it
+    /// runs on. `callback` is invoked synchronously for every keystroke,
+    /// on that thread, and its return value decides whether the keystroke
+    /// is swallowed; every keystroke is also pushed to [`KeyboardHook::events`]
+    /// regardless of the decision.
+    pub fn install(
+        mut callback: impl FnMut(&HookEvent) -> HookAction + Send + 'static,
+    ) -> Result<Self> {
+        let (events_tx, events_rx) = mpsc::channel();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<u32>>();
+
+        let thread = std::thread::spawn(move || {
+            STATE.with(|state| {
+                *state.borrow_mut() = Some(HookState {
+                    callback: Box::new(move |event| callback(event)),
+                    events: events_tx,
+                });
+            });
+
+            // SAFETY: `hook_proc` matches the `HOOKPROC` signature and the
+            // `None` module handle is required for a hook installed on the
+            // calling thread rather than injected into another process.
+            let hook = unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(hook_proc), None, 0) };
+            let hook = match hook {
+                Ok(hook) => hook,
+                Err(err) => {
+                    let _ = ready_tx.send(Err(Error::from(err)));
+                    return;
+                }
+            };
+
+            // SAFETY: `GetCurrentThreadId` is always safe to call.
+            let _ = ready_tx.send(Ok(unsafe { GetCurrentThreadId() }));
+
+            let mut msg = MSG::default();
+            // SAFETY: `msg` is a valid out-parameter for the duration of
+            // each call; the loop exits once `PostThreadMessageW` below
+            // posts a `WM_QUIT`.
+            while unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {
+                unsafe {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+
+            // SAFETY: `hook` was installed by this same thread above and
+            // is still installed.
+            let _ = unsafe { UnhookWindowsHookEx(hook) };
+        });
+
+        let thread_id = ready_rx
+            .recv()
+            .map_err(|_| Error::from(windows::core::Error::from_win32()))??;
+
+        Ok(Self {
+            thread: Some(thread),
+            thread_id,
+            events: events_rx,
+        })
+    }
+
+    /// The channel of keystrokes observed by this hook, in order.
+    pub fn events(&self) -> &Receiver<HookEvent> {
+        &self.events
+    }
+}
+
+impl Drop for KeyboardHook {
+    fn drop(&mut self) {
+        // SAFETY: posting a parameterless `WM_QUIT` to the hook's own
+        // thread is always safe; it just breaks that thread's message
+        // loop above.
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+unsafe extern "system" fn hook_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code as u32 == HC_ACTION {
+        // SAFETY: per the `WH_KEYBOARD_LL` contract, `lparam` points to a
+        // valid `KBDLLHOOKSTRUCT` for the duration of this call.
+        let info = unsafe { *(lparam.0 as *const KBDLLHOOKSTRUCT) };
+        let event = HookEvent {
+            code: KeyCode::try_from(VIRTUAL_KEY(info.vkCode as u16)).ok(),
+            pressed: matches!(wparam.0 as u32, WM_KEYDOWN | WM_SYSKEYDOWN),
+            scan_code: info.scanCode,
+        };
+
+        let action = STATE.with(|state| {
+            let mut state = state.borrow_mut();
+            match state.as_mut() {
+                Some(state) => {
+                    let action = (state.callback)(&event);
+                    let _ = state.events.send(event);
+                    action
+                }
+                None => HookAction::PassThrough,
+            }
+        });
+
+        if action == HookAction::Swallow {
+            return LRESULT(1);
+        }
+    }
+
+    // SAFETY: `code`, `wparam`, and `lparam` are forwarded unmodified from
+    // this hook procedure's own parameters, as `CallNextHookEx` requires.
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
@@ -0,0 +1,1112 @@
+//! Keyboard state tracking: pressed-key queries and a WM_CHAR/WM_UNICHAR
+//! text buffer.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    GetKeyState, MapVirtualKeyExW, VkKeyScanW, HKL, MAPVK_VK_TO_CHAR, VIRTUAL_KEY, VK_CAPITAL,
+    VK_NUMLOCK, VK_SCROLL,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetMessageTime, UNICODE_NOCHAR, WM_CHAR, WM_KEYDOWN, WM_KEYUP, WM_UNICHAR,
+};
+
+/// A layout-independent-ish virtual key, modeled after the Win32 virtual-key
+/// codes.
+///
+/// This initial set covers the keys most applications bind directly; see
+/// [`KeyCode::try_from`] for the mapping from raw virtual-key codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum KeyCode {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Escape,
+    Tab,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    Shift,
+    Control,
+    Alt,
+    Space,
+    Enter,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadSeparator,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    Semicolon,
+    Equal,
+    Comma,
+    Minus,
+    Period,
+    Slash,
+    Backquote,
+    LeftBracket,
+    Backslash,
+    RightBracket,
+    Quote,
+    BrowserBack,
+    BrowserForward,
+    BrowserRefresh,
+    BrowserStop,
+    BrowserSearch,
+    BrowserFavorites,
+    BrowserHome,
+    MediaNextTrack,
+    MediaPreviousTrack,
+    MediaStop,
+    MediaPlayPause,
+    VolumeMute,
+    VolumeDown,
+    VolumeUp,
+    LaunchMail,
+    LaunchMediaSelect,
+    LaunchApp1,
+    LaunchApp2,
+    ImeKana,
+    ImeJunja,
+    ImeFinal,
+    ImeKanji,
+    ImeConvert,
+    ImeNonConvert,
+    ImeAccept,
+    ImeModeChange,
+    ImeOn,
+    ImeOff,
+    ImeProcessKey,
+}
+
+impl TryFrom<VIRTUAL_KEY> for KeyCode {
+    type Error = ();
+
+    fn try_from(vk: VIRTUAL_KEY) -> Result<Self, Self::Error> {
+        use windows::Win32::UI::Input::KeyboardAndMouse::*;
+
+        Ok(match vk {
+            VK_A => Self::A,
+            VK_B => Self::B,
+            VK_C => Self::C,
+            VK_D => Self::D,
+            VK_E => Self::E,
+            VK_F => Self::F,
+            VK_G => Self::G,
+            VK_H => Self::H,
+            VK_I => Self::I,
+            VK_J => Self::J,
+            VK_K => Self::K,
+            VK_L => Self::L,
+            VK_M => Self::M,
+            VK_N => Self::N,
+            VK_O => Self::O,
+            VK_P => Self::P,
+            VK_Q => Self::Q,
+            VK_R => Self::R,
+            VK_S => Self::S,
+            VK_T => Self::T,
+            VK_U => Self::U,
+            VK_V => Self::V,
+            VK_W => Self::W,
+            VK_X => Self::X,
+            VK_Y => Self::Y,
+            VK_Z => Self::Z,
+            VK_0 => Self::Digit0,
+            VK_1 => Self::Digit1,
+            VK_2 => Self::Digit2,
+            VK_3 => Self::Digit3,
+            VK_4 => Self::Digit4,
+            VK_5 => Self::Digit5,
+            VK_6 => Self::Digit6,
+            VK_7 => Self::Digit7,
+            VK_8 => Self::Digit8,
+            VK_9 => Self::Digit9,
+            VK_F1 => Self::F1,
+            VK_F2 => Self::F2,
+            VK_F3 => Self::F3,
+            VK_F4 => Self::F4,
+            VK_F5 => Self::F5,
+            VK_F6 => Self::F6,
+            VK_F7 => Self::F7,
+            VK_F8 => Self::F8,
+            VK_F9 => Self::F9,
+            VK_F10 => Self::F10,
+            VK_F11 => Self::F11,
+            VK_F12 => Self::F12,
+            VK_ESCAPE => Self::Escape,
+            VK_TAB => Self::Tab,
+            VK_CAPITAL => Self::CapsLock,
+            VK_NUMLOCK => Self::NumLock,
+            VK_SCROLL => Self::ScrollLock,
+            VK_SHIFT => Self::Shift,
+            VK_CONTROL => Self::Control,
+            VK_MENU => Self::Alt,
+            VK_SPACE => Self::Space,
+            VK_RETURN => Self::Enter,
+            VK_BACK => Self::Backspace,
+            VK_DELETE => Self::Delete,
+            VK_INSERT => Self::Insert,
+            VK_HOME => Self::Home,
+            VK_END => Self::End,
+            VK_PRIOR => Self::PageUp,
+            VK_NEXT => Self::PageDown,
+            VK_LEFT => Self::ArrowLeft,
+            VK_RIGHT => Self::ArrowRight,
+            VK_UP => Self::ArrowUp,
+            VK_DOWN => Self::ArrowDown,
+            VK_NUMPAD0 => Self::Numpad0,
+            VK_NUMPAD1 => Self::Numpad1,
+            VK_NUMPAD2 => Self::Numpad2,
+            VK_NUMPAD3 => Self::Numpad3,
+            VK_NUMPAD4 => Self::Numpad4,
+            VK_NUMPAD5 => Self::Numpad5,
+            VK_NUMPAD6 => Self::Numpad6,
+            VK_NUMPAD7 => Self::Numpad7,
+            VK_NUMPAD8 => Self::Numpad8,
+            VK_NUMPAD9 => Self::Numpad9,
+            VK_ADD => Self::NumpadAdd,
+            VK_SUBTRACT => Self::NumpadSubtract,
+            VK_MULTIPLY => Self::NumpadMultiply,
+            VK_DIVIDE => Self::NumpadDivide,
+            VK_DECIMAL => Self::NumpadDecimal,
+            VK_SEPARATOR => Self::NumpadSeparator,
+            VK_F13 => Self::F13,
+            VK_F14 => Self::F14,
+            VK_F15 => Self::F15,
+            VK_F16 => Self::F16,
+            VK_F17 => Self::F17,
+            VK_F18 => Self::F18,
+            VK_F19 => Self::F19,
+            VK_F20 => Self::F20,
+            VK_F21 => Self::F21,
+            VK_F22 => Self::F22,
+            VK_F23 => Self::F23,
+            VK_F24 => Self::F24,
+            VK_OEM_1 => Self::Semicolon,
+            VK_OEM_PLUS => Self::Equal,
+            VK_OEM_COMMA => Self::Comma,
+            VK_OEM_MINUS => Self::Minus,
+            VK_OEM_PERIOD => Self::Period,
+            VK_OEM_2 => Self::Slash,
+            VK_OEM_3 => Self::Backquote,
+            VK_OEM_4 => Self::LeftBracket,
+            VK_OEM_5 => Self::Backslash,
+            VK_OEM_6 => Self::RightBracket,
+            VK_OEM_7 => Self::Quote,
+            VK_BROWSER_BACK => Self::BrowserBack,
+            VK_BROWSER_FORWARD => Self::BrowserForward,
+            VK_BROWSER_REFRESH => Self::BrowserRefresh,
+            VK_BROWSER_STOP => Self::BrowserStop,
+            VK_BROWSER_SEARCH => Self::BrowserSearch,
+            VK_BROWSER_FAVORITES => Self::BrowserFavorites,
+            VK_BROWSER_HOME => Self::BrowserHome,
+            VK_MEDIA_NEXT_TRACK => Self::MediaNextTrack,
+            VK_MEDIA_PREV_TRACK => Self::MediaPreviousTrack,
+            VK_MEDIA_STOP => Self::MediaStop,
+            VK_MEDIA_PLAY_PAUSE => Self::MediaPlayPause,
+            VK_VOLUME_MUTE => Self::VolumeMute,
+            VK_VOLUME_DOWN => Self::VolumeDown,
+            VK_VOLUME_UP => Self::VolumeUp,
+            VK_LAUNCH_MAIL => Self::LaunchMail,
+            VK_LAUNCH_MEDIA_SELECT => Self::LaunchMediaSelect,
+            VK_LAUNCH_APP1 => Self::LaunchApp1,
+            VK_LAUNCH_APP2 => Self::LaunchApp2,
+            VK_KANA => Self::ImeKana,
+            VK_JUNJA => Self::ImeJunja,
+            VK_FINAL => Self::ImeFinal,
+            VK_KANJI => Self::ImeKanji,
+            VK_CONVERT => Self::ImeConvert,
+            VK_NONCONVERT => Self::ImeNonConvert,
+            VK_ACCEPT => Self::ImeAccept,
+            VK_MODECHANGE => Self::ImeModeChange,
+            VK_IME_ON => Self::ImeOn,
+            VK_IME_OFF => Self::ImeOff,
+            VK_PROCESSKEY => Self::ImeProcessKey,
+            _ => return Err(()),
+        })
+    }
+}
+
+impl From<KeyCode> for VIRTUAL_KEY {
+    /// The inverse of [`KeyCode`]'s `TryFrom<VIRTUAL_KEY>` impl, so a
+    /// `KeyCode` round-trips back to the virtual-key code Windows expects,
+    /// e.g. for synthesizing input with `SendInput`.
+    fn from(code: KeyCode) -> Self {
+        use windows::Win32::UI::Input::KeyboardAndMouse::*;
+
+        match code {
+            KeyCode::A => VK_A,
+            KeyCode::B => VK_B,
+            KeyCode::C => VK_C,
+            KeyCode::D => VK_D,
+            KeyCode::E => VK_E,
+            KeyCode::F => VK_F,
+            KeyCode::G => VK_G,
+            KeyCode::H => VK_H,
+            KeyCode::I => VK_I,
+            KeyCode::J => VK_J,
+            KeyCode::K => VK_K,
+            KeyCode::L => VK_L,
+            KeyCode::M => VK_M,
+            KeyCode::N => VK_N,
+            KeyCode::O => VK_O,
+            KeyCode::P => VK_P,
+            KeyCode::Q => VK_Q,
+            KeyCode::R => VK_R,
+            KeyCode::S => VK_S,
+            KeyCode::T => VK_T,
+            KeyCode::U => VK_U,
+            KeyCode::V => VK_V,
+            KeyCode::W => VK_W,
+            KeyCode::X => VK_X,
+            KeyCode::Y => VK_Y,
+            KeyCode::Z => VK_Z,
+            KeyCode::Digit0 => VK_0,
+            KeyCode::Digit1 => VK_1,
+            KeyCode::Digit2 => VK_2,
+            KeyCode::Digit3 => VK_3,
+            KeyCode::Digit4 => VK_4,
+            KeyCode::Digit5 => VK_5,
+            KeyCode::Digit6 => VK_6,
+            KeyCode::Digit7 => VK_7,
+            KeyCode::Digit8 => VK_8,
+            KeyCode::Digit9 => VK_9,
+            KeyCode::F1 => VK_F1,
+            KeyCode::F2 => VK_F2,
+            KeyCode::F3 => VK_F3,
+            KeyCode::F4 => VK_F4,
+            KeyCode::F5 => VK_F5,
+            KeyCode::F6 => VK_F6,
+            KeyCode::F7 => VK_F7,
+            KeyCode::F8 => VK_F8,
+            KeyCode::F9 => VK_F9,
+            KeyCode::F10 => VK_F10,
+            KeyCode::F11 => VK_F11,
+            KeyCode::F12 => VK_F12,
+            KeyCode::Escape => VK_ESCAPE,
+            KeyCode::Tab => VK_TAB,
+            KeyCode::CapsLock => VK_CAPITAL,
+            KeyCode::NumLock => VK_NUMLOCK,
+            KeyCode::ScrollLock => VK_SCROLL,
+            KeyCode::Shift => VK_SHIFT,
+            KeyCode::Control => VK_CONTROL,
+            KeyCode::Alt => VK_MENU,
+            KeyCode::Space => VK_SPACE,
+            KeyCode::Enter => VK_RETURN,
+            KeyCode::Backspace => VK_BACK,
+            KeyCode::Delete => VK_DELETE,
+            KeyCode::Insert => VK_INSERT,
+            KeyCode::Home => VK_HOME,
+            KeyCode::End => VK_END,
+            KeyCode::PageUp => VK_PRIOR,
+            KeyCode::PageDown => VK_NEXT,
+            KeyCode::ArrowLeft => VK_LEFT,
+            KeyCode::ArrowRight => VK_RIGHT,
+            KeyCode::ArrowUp => VK_UP,
+            KeyCode::ArrowDown => VK_DOWN,
+            KeyCode::Numpad0 => VK_NUMPAD0,
+            KeyCode::Numpad1 => VK_NUMPAD1,
+            KeyCode::Numpad2 => VK_NUMPAD2,
+            KeyCode::Numpad3 => VK_NUMPAD3,
+            KeyCode::Numpad4 => VK_NUMPAD4,
+            KeyCode::Numpad5 => VK_NUMPAD5,
+            KeyCode::Numpad6 => VK_NUMPAD6,
+            KeyCode::Numpad7 => VK_NUMPAD7,
+            KeyCode::Numpad8 => VK_NUMPAD8,
+            KeyCode::Numpad9 => VK_NUMPAD9,
+            KeyCode::NumpadAdd => VK_ADD,
+            KeyCode::NumpadSubtract => VK_SUBTRACT,
+            KeyCode::NumpadMultiply => VK_MULTIPLY,
+            KeyCode::NumpadDivide => VK_DIVIDE,
+            KeyCode::NumpadDecimal => VK_DECIMAL,
+            KeyCode::NumpadSeparator => VK_SEPARATOR,
+            KeyCode::F13 => VK_F13,
+            KeyCode::F14 => VK_F14,
+            KeyCode::F15 => VK_F15,
+            KeyCode::F16 => VK_F16,
+            KeyCode::F17 => VK_F17,
+            KeyCode::F18 => VK_F18,
+            KeyCode::F19 => VK_F19,
+            KeyCode::F20 => VK_F20,
+            KeyCode::F21 => VK_F21,
+            KeyCode::F22 => VK_F22,
+            KeyCode::F23 => VK_F23,
+            KeyCode::F24 => VK_F24,
+            KeyCode::Semicolon => VK_OEM_1,
+            KeyCode::Equal => VK_OEM_PLUS,
+            KeyCode::Comma => VK_OEM_COMMA,
+            KeyCode::Minus => VK_OEM_MINUS,
+            KeyCode::Period => VK_OEM_PERIOD,
+            KeyCode::Slash => VK_OEM_2,
+            KeyCode::Backquote => VK_OEM_3,
+            KeyCode::LeftBracket => VK_OEM_4,
+            KeyCode::Backslash => VK_OEM_5,
+            KeyCode::RightBracket => VK_OEM_6,
+            KeyCode::Quote => VK_OEM_7,
+            KeyCode::BrowserBack => VK_BROWSER_BACK,
+            KeyCode::BrowserForward => VK_BROWSER_FORWARD,
+            KeyCode::BrowserRefresh => VK_BROWSER_REFRESH,
+            KeyCode::BrowserStop => VK_BROWSER_STOP,
+            KeyCode::BrowserSearch => VK_BROWSER_SEARCH,
+            KeyCode::BrowserFavorites => VK_BROWSER_FAVORITES,
+            KeyCode::BrowserHome => VK_BROWSER_HOME,
+            KeyCode::MediaNextTrack => VK_MEDIA_NEXT_TRACK,
+            KeyCode::MediaPreviousTrack => VK_MEDIA_PREV_TRACK,
+            KeyCode::MediaStop => VK_MEDIA_STOP,
+            KeyCode::MediaPlayPause => VK_MEDIA_PLAY_PAUSE,
+            KeyCode::VolumeMute => VK_VOLUME_MUTE,
+            KeyCode::VolumeDown => VK_VOLUME_DOWN,
+            KeyCode::VolumeUp => VK_VOLUME_UP,
+            KeyCode::LaunchMail => VK_LAUNCH_MAIL,
+            KeyCode::LaunchMediaSelect => VK_LAUNCH_MEDIA_SELECT,
+            KeyCode::LaunchApp1 => VK_LAUNCH_APP1,
+            KeyCode::LaunchApp2 => VK_LAUNCH_APP2,
+            KeyCode::ImeKana => VK_KANA,
+            KeyCode::ImeJunja => VK_JUNJA,
+            KeyCode::ImeFinal => VK_FINAL,
+            KeyCode::ImeKanji => VK_KANJI,
+            KeyCode::ImeConvert => VK_CONVERT,
+            KeyCode::ImeNonConvert => VK_NONCONVERT,
+            KeyCode::ImeAccept => VK_ACCEPT,
+            KeyCode::ImeModeChange => VK_MODECHANGE,
+            KeyCode::ImeOn => VK_IME_ON,
+            KeyCode::ImeOff => VK_IME_OFF,
+            KeyCode::ImeProcessKey => VK_PROCESSKEY,
+        }
+    }
+}
+
+impl KeyCode {
+    /// Translates this key into the character it produces under `layout`
+    /// (as returned by `GetKeyboardLayout`), or `None` if it has no
+    /// character mapping (e.g. function keys) or only begins a dead-key
+    /// sequence. Intended for key-binding UIs that want to show the user a
+    /// printable label rather than a raw key name.
+    pub fn to_char(self, layout: HKL) -> Option<char> {
+        // SAFETY: always safe to call with any virtual-key code and any
+        // layout handle, live or not.
+        let mapped = unsafe {
+            MapVirtualKeyExW(
+                VIRTUAL_KEY::from(self).0 as u32,
+                MAPVK_VK_TO_CHAR,
+                Some(layout),
+            )
+        };
+        // Zero means no translation; the high bit set means this is a dead
+        // key rather than a directly printable character.
+        if mapped == 0 || mapped & 0x8000_0000 != 0 {
+            return None;
+        }
+        char::from_u32(mapped & 0xffff)
+    }
+
+    /// Translates `c` into the key that produces it under the calling
+    /// thread's current keyboard layout, or `None` if no key on the active
+    /// layout produces `c`.
+    pub fn from_char(c: char) -> Option<Self> {
+        let mut buf = [0u16; 2];
+        let units = c.encode_utf16(&mut buf);
+        if units.len() != 1 {
+            return None;
+        }
+        // SAFETY: always safe to call.
+        let scan = unsafe { VkKeyScanW(units[0]) };
+        if scan == -1 {
+            return None;
+        }
+        Self::try_from(VIRTUAL_KEY(scan as u16 & 0xff)).ok()
+    }
+}
+
+/// A layout-independent physical key identity, derived from the OEM scan
+/// code rather than the virtual key, so e.g. `PhysicalKey::W` always names
+/// the key in the QWERTY "W" position on the keyboard even under a layout
+/// (AZERTY, Dvorak, ...) that maps it to a different character or virtual
+/// key. WASD-style games should bind against this rather than [`KeyCode`].
+///
+/// This initial set covers the keys most games bind directly; see
+/// [`PhysicalKey::from_scan_code`] for the mapping from raw scan codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum PhysicalKey {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Escape,
+    Tab,
+    CapsLock,
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+    LeftAlt,
+    RightAlt,
+    Space,
+    Enter,
+    Backspace,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+}
+
+impl PhysicalKey {
+    /// Maps a raw OEM scan code, and whether it carried the `WM_KEYDOWN`/
+    /// `WM_KEYUP` extended-key flag, to a [`PhysicalKey`].
+    ///
+    /// The extended flag disambiguates keys that share a scan code between
+    /// their main-block and numpad/right-hand forms (e.g. `0x1D` is left
+    /// Control unless extended, in which case it's right Control).
+    pub fn from_scan_code(scan_code: u8, extended: bool) -> Option<Self> {
+        Some(match (scan_code, extended) {
+            (0x10, false) => Self::Q,
+            (0x11, false) => Self::W,
+            (0x12, false) => Self::E,
+            (0x13, false) => Self::R,
+            (0x14, false) => Self::T,
+            (0x15, false) => Self::Y,
+            (0x16, false) => Self::U,
+            (0x17, false) => Self::I,
+            (0x18, false) => Self::O,
+            (0x19, false) => Self::P,
+            (0x1e, false) => Self::A,
+            (0x1f, false) => Self::S,
+            (0x20, false) => Self::D,
+            (0x21, false) => Self::F,
+            (0x22, false) => Self::G,
+            (0x23, false) => Self::H,
+            (0x24, false) => Self::J,
+            (0x25, false) => Self::K,
+            (0x26, false) => Self::L,
+            (0x2c, false) => Self::Z,
+            (0x2d, false) => Self::X,
+            (0x2e, false) => Self::C,
+            (0x2f, false) => Self::V,
+            (0x30, false) => Self::B,
+            (0x31, false) => Self::N,
+            (0x32, false) => Self::M,
+            (0x02, false) => Self::Digit1,
+            (0x03, false) => Self::Digit2,
+            (0x04, false) => Self::Digit3,
+            (0x05, false) => Self::Digit4,
+            (0x06, false) => Self::Digit5,
+            (0x07, false) => Self::Digit6,
+            (0x08, false) => Self::Digit7,
+            (0x09, false) => Self::Digit8,
+            (0x0a, false) => Self::Digit9,
+            (0x0b, false) => Self::Digit0,
+            (0x3b, false) => Self::F1,
+            (0x3c, false) => Self::F2,
+            (0x3d, false) => Self::F3,
+            (0x3e, false) => Self::F4,
+            (0x3f, false) => Self::F5,
+            (0x40, false) => Self::F6,
+            (0x41, false) => Self::F7,
+            (0x42, false) => Self::F8,
+            (0x43, false) => Self::F9,
+            (0x44, false) => Self::F10,
+            (0x57, false) => Self::F11,
+            (0x58, false) => Self::F12,
+            (0x01, false) => Self::Escape,
+            (0x0f, false) => Self::Tab,
+            (0x3a, false) => Self::CapsLock,
+            (0x2a, false) => Self::LeftShift,
+            (0x36, false) => Self::RightShift,
+            (0x1d, false) => Self::LeftControl,
+            (0x1d, true) => Self::RightControl,
+            (0x38, false) => Self::LeftAlt,
+            (0x38, true) => Self::RightAlt,
+            (0x39, false) => Self::Space,
+            (0x1c, false) => Self::Enter,
+            (0x1c, true) => Self::Enter,
+            (0x0e, false) => Self::Backspace,
+            (0x4b, true) => Self::ArrowLeft,
+            (0x4d, true) => Self::ArrowRight,
+            (0x48, true) => Self::ArrowUp,
+            (0x50, true) => Self::ArrowDown,
+            _ => return None,
+        })
+    }
+}
+
+/// A fixed-size 256-bit set, indexed by virtual-key code, used to track
+/// which keys are currently held down without allocating on every keystroke.
+#[derive(Debug, Default)]
+struct KeyBitset([u64; 4]);
+
+impl KeyBitset {
+    fn set(&mut self, vk: u8, pressed: bool) {
+        let word = &mut self.0[(vk >> 6) as usize];
+        let bit = 1u64 << (vk & 0x3f);
+        if pressed {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    fn get(&self, vk: u8) -> bool {
+        (self.0[(vk >> 6) as usize] >> (vk & 0x3f)) & 1 != 0
+    }
+}
+
+/// The flags packed into the `lParam` of `WM_KEYDOWN`/`WM_KEYUP`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeystrokeFlags {
+    /// Number of times the keystroke is auto-repeated as a result of the
+    /// user holding the key down.
+    pub repeat_count: u16,
+    /// The OEM scan code.
+    pub scan_code: u8,
+    /// Whether the key is an "extended" key (e.g. right-hand Ctrl/Alt, the
+    /// arrow cluster, or numeric keypad Enter).
+    pub extended: bool,
+    /// Whether the Alt key was held down when the message was generated.
+    pub alt_down: bool,
+    /// The state of the key before this message (for `WM_KEYUP` this is
+    /// always `true`).
+    pub was_down: bool,
+    /// The transition state: `false` for a key press, `true` for a release.
+    pub transition_up: bool,
+}
+
+impl KeystrokeFlags {
+    fn from_lparam(lparam: LPARAM) -> Self {
+        let bits = lparam.0 as u32;
+        Self {
+            repeat_count: (bits & 0xffff) as u16,
+            scan_code: ((bits >> 16) & 0xff) as u8,
+            extended: (bits >> 24) & 1 != 0,
+            alt_down: (bits >> 29) & 1 != 0,
+            was_down: (bits >> 30) & 1 != 0,
+            transition_up: (bits >> 31) & 1 != 0,
+        }
+    }
+}
+
+/// A single keyboard transition, as delivered by the window message loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub code: KeyCode,
+    pub pressed: bool,
+    pub flags: KeystrokeFlags,
+    /// Time since system startup at which the underlying message was
+    /// created, per `GetMessageTime`. Useful for double-tap detection;
+    /// compare two events' timestamps rather than treating this as wall
+    /// clock time.
+    pub timestamp: Duration,
+}
+
+impl KeyEvent {
+    pub(crate) fn new(msg: u32, wparam: WPARAM, lparam: LPARAM) -> Option<Self> {
+        let code = KeyCode::try_from(VIRTUAL_KEY(wparam.0 as u16)).ok()?;
+        Some(Self {
+            code,
+            pressed: msg == WM_KEYDOWN,
+            flags: KeystrokeFlags::from_lparam(lparam),
+            // SAFETY: always valid to call while processing a message.
+            timestamp: Duration::from_millis(unsafe { GetMessageTime() } as u32 as u64),
+        })
+    }
+
+    /// The layout-independent physical key this event occurred on, if it
+    /// maps to one of [`PhysicalKey`]'s initial set.
+    pub fn physical_key(&self) -> Option<PhysicalKey> {
+        PhysicalKey::from_scan_code(self.flags.scan_code, self.flags.extended)
+    }
+}
+
+/// Accumulates text delivered via `WM_CHAR`, honoring backspace.
+#[derive(Debug, Default)]
+pub(crate) struct InputBuffer {
+    buf: String,
+    /// Forward deletes (the Delete key) accumulated since the last call to
+    /// [`InputBuffer::take_deletes`]. The buffer has no notion of a caret,
+    /// so these apply to text *after* the caller's own caret rather than
+    /// to `buf`.
+    deletes: u32,
+}
+
+impl InputBuffer {
+    fn push_char(&mut self, c: char) {
+        self.buf.push(c);
+    }
+
+    fn backspace(&mut self) {
+        self.buf.pop();
+    }
+
+    /// Records a forward delete (Delete key press).
+    fn delete(&mut self) {
+        self.deletes += 1;
+    }
+
+    /// Removes and returns all text accumulated so far.
+    fn drain(&mut self) -> String {
+        std::mem::take(&mut self.buf)
+    }
+
+    /// Removes and returns the number of forward deletes accumulated since
+    /// the last call.
+    fn take_deletes(&mut self) -> u32 {
+        std::mem::take(&mut self.deletes)
+    }
+}
+
+/// Tracks currently-pressed keys and accumulated text input for a window.
+pub struct Keyboard {
+    pressed: KeyBitset,
+    physical_pressed: KeyBitset,
+    input: InputBuffer,
+    caps_lock: bool,
+    num_lock: bool,
+    scroll_lock: bool,
+    /// Raw key events not yet consumed via [`Keyboard::drain_events`].
+    events: VecDeque<KeyEvent>,
+    /// The tick count at which each currently-held key was first pressed,
+    /// used by [`Keyboard::key_held_duration`].
+    pressed_since: HashMap<KeyCode, u32>,
+    /// Set via [`Keyboard::set_text_input_enabled`]; while `false`, `WM_CHAR`
+    /// is ignored so held-down "action" keys don't leak into a text buffer
+    /// nobody is reading.
+    text_input_enabled: bool,
+    /// Callbacks registered via [`Keyboard::on_key_event`], fired
+    /// synchronously from [`Keyboard::process_evt`].
+    observers: Vec<Box<dyn FnMut(&KeyEvent)>>,
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self {
+            pressed: KeyBitset::default(),
+            physical_pressed: KeyBitset::default(),
+            input: InputBuffer::default(),
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
+            events: VecDeque::new(),
+            pressed_since: HashMap::new(),
+            text_input_enabled: true,
+            observers: Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Keyboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keyboard")
+            .field("pressed", &self.pressed)
+            .field("physical_pressed", &self.physical_pressed)
+            .field("input", &self.input)
+            .field("caps_lock", &self.caps_lock)
+            .field("num_lock", &self.num_lock)
+            .field("scroll_lock", &self.scroll_lock)
+            .field("events", &self.events)
+            .field("pressed_since", &self.pressed_since)
+            .field("text_input_enabled", &self.text_input_enabled)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl Keyboard {
+    /// Returns `true` if `code` is currently held down.
+    pub fn is_pressed(&self, code: KeyCode) -> bool {
+        self.pressed.get(code as u8)
+    }
+
+    /// Returns `true` if the physical key `key` is currently held down,
+    /// regardless of what virtual key the active layout maps it to.
+    pub fn is_physical_key_pressed(&self, key: PhysicalKey) -> bool {
+        self.physical_pressed.get(key as u8)
+    }
+
+    /// Returns `true` if Caps Lock is currently toggled on.
+    pub fn is_caps_lock_on(&self) -> bool {
+        self.caps_lock
+    }
+
+    /// Returns `true` if Num Lock is currently toggled on.
+    pub fn is_num_lock_on(&self) -> bool {
+        self.num_lock
+    }
+
+    /// Returns `true` if Scroll Lock is currently toggled on.
+    pub fn is_scroll_lock_on(&self) -> bool {
+        self.scroll_lock
+    }
+
+    /// Removes and returns all text accumulated since the last call.
+    pub fn drain_input(&mut self) -> String {
+        self.input.drain()
+    }
+
+    /// Removes and returns the number of times the Delete key was pressed
+    /// since the last call. Unlike backspace, a forward delete removes text
+    /// *after* the caret, which this buffer has no notion of, so the caller
+    /// is expected to apply that many deletes to its own text at the
+    /// caret's position.
+    pub fn num_deletes(&mut self) -> u32 {
+        self.input.take_deletes()
+    }
+
+    /// Enables or disables `WM_CHAR` text accumulation, for applications
+    /// that switch between "action" input (where keys are bindings, not
+    /// text) and a text field such as a chat box. Enabled by default.
+    pub fn set_text_input_enabled(&mut self, enabled: bool) {
+        self.text_input_enabled = enabled;
+    }
+
+    /// Returns `true` if `WM_CHAR` text accumulation is currently enabled.
+    pub fn is_text_input_enabled(&self) -> bool {
+        self.text_input_enabled
+    }
+
+    /// Removes and returns all raw key down/up events accumulated since the
+    /// last call, including auto-repeat, for callers that need to implement
+    /// their own key handling rather than rely on [`Keyboard::is_pressed`]
+    /// or [`Keyboard::drain_input`].
+    pub fn drain_events(&mut self) -> Vec<KeyEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Registers `callback` to be invoked synchronously, in
+    /// [`Keyboard::process_evt`], for every key down/up event, including
+    /// auto-repeat. Unlike [`Keyboard::drain_events`], observers see events
+    /// the moment they arrive rather than on the next poll, for low-latency
+    /// shortcut handlers and macro recorders. Registered callbacks are never
+    /// unregistered and live for as long as the `Keyboard` does.
+    pub fn on_key_event(&mut self, callback: impl FnMut(&KeyEvent) + 'static) {
+        self.observers.push(Box::new(callback));
+    }
+
+    /// How long `code` has been continuously held down, or `None` if it
+    /// isn't currently pressed. Useful for charge-up mechanics and
+    /// double-tap detection.
+    pub fn key_held_duration(&self, code: KeyCode) -> Option<Duration> {
+        let pressed_since = *self.pressed_since.get(&code)?;
+        // SAFETY: always valid to call.
+        let now = unsafe { GetTickCount() };
+        Some(Duration::from_millis(now.wrapping_sub(pressed_since) as u64))
+    }
+
+    /// Clears all pressed-key state, e.g. when the window loses focus, and
+    /// resyncs toggle-key state directly via `GetKeyState`, since toggles
+    /// can change while the window isn't focused to observe the key
+    /// events that would otherwise track them.
+    pub(crate) fn reset(&mut self) {
+        self.pressed = KeyBitset::default();
+        self.physical_pressed = KeyBitset::default();
+        self.pressed_since.clear();
+        self.sync_toggle_keys();
+    }
+
+    /// Resyncs toggle-key state directly via `GetKeyState`, e.g. when the
+    /// window regains focus.
+    pub(crate) fn sync_toggle_keys(&mut self) {
+        // SAFETY: `GetKeyState` is always safe to call.
+        self.caps_lock = unsafe { GetKeyState(VK_CAPITAL.0 as i32) } & 1 != 0;
+        // SAFETY: `GetKeyState` is always safe to call.
+        self.num_lock = unsafe { GetKeyState(VK_NUMLOCK.0 as i32) } & 1 != 0;
+        // SAFETY: `GetKeyState` is always safe to call.
+        self.scroll_lock = unsafe { GetKeyState(VK_SCROLL.0 as i32) } & 1 != 0;
+    }
+
+    /// Appends a single UTF-32 code point (from `WM_CHAR` or `WM_UNICHAR`)
+    /// to the input buffer, honoring backspace and dropping other control
+    /// characters.
+    fn push_code_point(&mut self, c: u32) {
+        match c {
+            0x08 => self.input.backspace(),
+            // Control characters other than backspace carry no printable
+            // text and are dropped.
+            0x00..=0x1f => {}
+            _ => {
+                if let Some(c) = char::from_u32(c) {
+                    self.input.push_char(c);
+                }
+            }
+        }
+    }
+
+    /// Feeds a raw window message into the keyboard's state machine.
+    pub(crate) fn process_evt(&mut self, msg: u32, wparam: WPARAM, lparam: LPARAM) {
+        match msg {
+            WM_KEYDOWN | WM_KEYUP => {
+                if let Some(evt) = KeyEvent::new(msg, wparam, lparam) {
+                    for observer in &mut self.observers {
+                        observer(&evt);
+                    }
+
+                    if evt.code == KeyCode::Alt && evt.pressed && evt.flags.extended {
+                        // AltGr is reported as a synthetic left-Control
+                        // down immediately followed by an extended (i.e.
+                        // right-hand) Alt down. Clear the synthetic
+                        // Control press so it doesn't falsely combine with
+                        // Alt to look like a Ctrl+Alt chord.
+                        self.pressed.set(KeyCode::Control as u8, false);
+                    }
+
+                    // Toggle-key state flips on every genuine press, not on
+                    // auto-repeated continuations of a held key.
+                    if evt.pressed && !evt.flags.was_down {
+                        match evt.code {
+                            KeyCode::CapsLock => self.caps_lock = !self.caps_lock,
+                            KeyCode::NumLock => self.num_lock = !self.num_lock,
+                            KeyCode::ScrollLock => self.scroll_lock = !self.scroll_lock,
+                            _ => {}
+                        }
+                    }
+
+                    if evt.pressed {
+                        self.pressed_since
+                            .entry(evt.code)
+                            .or_insert_with(|| unsafe { GetTickCount() });
+                    } else {
+                        self.pressed_since.remove(&evt.code);
+                    }
+
+                    if let Some(physical) = evt.physical_key() {
+                        self.physical_pressed.set(physical as u8, evt.pressed);
+                    }
+
+                    // Delete doesn't deliver a `WM_CHAR`, so it's tracked
+                    // here instead, like backspace counting every
+                    // auto-repeated press while the key is held down.
+                    if evt.code == KeyCode::Delete && evt.pressed && self.text_input_enabled {
+                        self.input.delete();
+                    }
+
+                    self.pressed.set(evt.code as u8, evt.pressed);
+                    self.events.push_back(evt);
+                }
+            }
+            WM_CHAR => {
+                if self.text_input_enabled {
+                    self.push_code_point(wparam.0 as u32);
+                }
+            }
+            WM_UNICHAR => {
+                // `UNICODE_NOCHAR` is a capability probe, not an actual code
+                // point; the window procedure answers it directly and
+                // nothing is fed into the input buffer.
+                if self.text_input_enabled && wparam.0 as u32 != UNICODE_NOCHAR {
+                    self.push_code_point(wparam.0 as u32);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+
+    use super::*;
+
+    fn keydown_lparam(extended: bool) -> LPARAM {
+        let mut bits: u32 = 1;
+        if extended {
+            bits |= 1 << 24;
+        }
+        LPARAM(bits as isize)
+    }
+
+    #[test]
+    fn altgr_suppresses_synthetic_control() {
+        let mut keyboard = Keyboard::default();
+        // AltGr: a synthetic, non-extended left-Control down immediately
+        // followed by an extended (right-hand) Alt down.
+        keyboard.process_evt(
+            WM_KEYDOWN,
+            WPARAM(VIRTUAL_KEY::from(KeyCode::Control).0 as usize),
+            keydown_lparam(false),
+        );
+        keyboard.process_evt(
+            WM_KEYDOWN,
+            WPARAM(VIRTUAL_KEY::from(KeyCode::Alt).0 as usize),
+            keydown_lparam(true),
+        );
+
+        assert!(!keyboard.is_pressed(KeyCode::Control));
+        assert!(keyboard.is_pressed(KeyCode::Alt));
+    }
+
+    #[test]
+    fn real_ctrl_alt_chord_is_not_suppressed() {
+        let mut keyboard = Keyboard::default();
+        // A genuine Ctrl+Alt chord uses the non-extended (left-hand) Alt,
+        // so it must not be mistaken for AltGr's synthetic Control.
+        keyboard.process_evt(
+            WM_KEYDOWN,
+            WPARAM(VIRTUAL_KEY::from(KeyCode::Control).0 as usize),
+            keydown_lparam(false),
+        );
+        keyboard.process_evt(
+            WM_KEYDOWN,
+            WPARAM(VIRTUAL_KEY::from(KeyCode::Alt).0 as usize),
+            keydown_lparam(false),
+        );
+
+        assert!(keyboard.is_pressed(KeyCode::Control));
+        assert!(keyboard.is_pressed(KeyCode::Alt));
+    }
+
+    fn current_layout() -> HKL {
+        // SAFETY: always safe to call; `0` queries the calling thread's
+        // own layout.
+        unsafe { GetKeyboardLayout(0) }
+    }
+
+    #[test]
+    fn to_char_round_trips_through_from_char() {
+        let layout = current_layout();
+        for code in [KeyCode::A, KeyCode::Z, KeyCode::Digit0, KeyCode::Digit9] {
+            let c = code
+                .to_char(layout)
+                .expect("letter/digit keys should map to a character");
+            assert_eq!(KeyCode::from_char(c), Some(code));
+        }
+    }
+
+    #[test]
+    fn function_keys_have_no_char_mapping() {
+        assert_eq!(KeyCode::F1.to_char(current_layout()), None);
+    }
+
+    #[test]
+    fn from_char_rejects_input_outside_the_basic_multilingual_plane() {
+        // Code points outside the BMP encode to two UTF-16 units, which
+        // `VkKeyScanW` can't look up.
+        assert_eq!(KeyCode::from_char('😀'), None);
+    }
+}
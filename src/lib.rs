@@ -1 +1,43 @@
+//! Lightweight management of native Win32 windows with high DPI awareness,
+//! input handling, and support for light and dark themes.
 
+pub mod app;
+pub mod clipboard;
+pub mod controls;
+pub mod debug;
+#[cfg(feature = "device_notifications")]
+pub mod device;
+pub mod dialog;
+mod dib;
+pub mod dpi;
+pub mod error;
+pub mod event_loop;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod geometry;
+pub mod gesture;
+pub mod graphics;
+pub mod handler;
+#[cfg(feature = "hook")]
+pub mod hook;
+pub mod keyboard;
+pub mod media;
+pub mod menu;
+pub mod mouse;
+pub mod power;
+pub mod proc;
+pub mod shell;
+pub mod shortcut;
+#[cfg(feature = "task_dialog")]
+pub mod task_dialog;
+pub mod theme;
+pub mod touch;
+mod util;
+pub mod window;
+
+pub use error::{Error, Result};
+pub use handler::{CloseResponse, WindowHandler};
+pub use theme::{Backdrop, Color, CornerPreference, Theme};
+pub use window::{
+    Builder, InitialState, Window, WindowExStyle, WindowHandle, WindowState, WindowStyle,
+};
@@ -0,0 +1,55 @@
+//! Media and browser navigation commands delivered via `WM_APPCOMMAND`
+//! (e.g. from a keyboard's dedicated media keys), exposed as a typed
+//! [`MediaCommand`] rather than the raw Win32 `APPCOMMAND_ID`.
+
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::System::SystemServices::{
+    APPCOMMAND_BROWSER_BACKWARD, APPCOMMAND_BROWSER_FORWARD, APPCOMMAND_ID,
+    APPCOMMAND_MEDIA_NEXTTRACK, APPCOMMAND_MEDIA_PAUSE, APPCOMMAND_MEDIA_PLAY,
+    APPCOMMAND_MEDIA_PLAY_PAUSE, APPCOMMAND_MEDIA_PREVIOUSTRACK, APPCOMMAND_MEDIA_STOP,
+    APPCOMMAND_VOLUME_DOWN, APPCOMMAND_VOLUME_MUTE, APPCOMMAND_VOLUME_UP,
+};
+use windows::Win32::UI::WindowsAndMessaging::FAPPCOMMAND_MASK;
+
+/// A media or browser navigation command delivered via `WM_APPCOMMAND`.
+///
+/// This initial set covers the keys most media apps bind directly; see
+/// [`MediaCommand::from_lparam`] for the mapping from the raw
+/// `APPCOMMAND_ID`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaCommand {
+    PlayPause,
+    Play,
+    Pause,
+    Stop,
+    NextTrack,
+    PreviousTrack,
+    VolumeUp,
+    VolumeDown,
+    VolumeMute,
+    BrowserBack,
+    BrowserForward,
+}
+
+impl MediaCommand {
+    /// Extracts the command from a `WM_APPCOMMAND` message's `lParam`, if
+    /// it maps to one of this initial set.
+    pub(crate) fn from_lparam(lparam: LPARAM) -> Option<Self> {
+        let raw = ((lparam.0 as u32) >> 16) & !FAPPCOMMAND_MASK;
+
+        Some(match APPCOMMAND_ID(raw) {
+            APPCOMMAND_MEDIA_PLAY_PAUSE => Self::PlayPause,
+            APPCOMMAND_MEDIA_PLAY => Self::Play,
+            APPCOMMAND_MEDIA_PAUSE => Self::Pause,
+            APPCOMMAND_MEDIA_STOP => Self::Stop,
+            APPCOMMAND_MEDIA_NEXTTRACK => Self::NextTrack,
+            APPCOMMAND_MEDIA_PREVIOUSTRACK => Self::PreviousTrack,
+            APPCOMMAND_VOLUME_UP => Self::VolumeUp,
+            APPCOMMAND_VOLUME_DOWN => Self::VolumeDown,
+            APPCOMMAND_VOLUME_MUTE => Self::VolumeMute,
+            APPCOMMAND_BROWSER_BACKWARD => Self::BrowserBack,
+            APPCOMMAND_BROWSER_FORWARD => Self::BrowserForward,
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,175 @@
+//! Menu bars and popup menus (`CreateMenu`/`AppendMenuW`), with selections
+//! delivered as typed events via the [`MenuId`] trait rather than raw
+//! `WM_COMMAND` IDs.
+
+use windows::core::PCWSTR;
+use windows::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreateMenu, CreatePopupMenu, DestroyMenu, HMENU, MF_CHECKED, MF_GRAYED, MF_POPUP,
+    MF_SEPARATOR, MF_STRING, MF_UNCHECKED,
+};
+
+use crate::error::{Error, Result};
+use crate::util::encode_wide;
+
+/// A type whose values identify selectable [`MenuBuilder::item`]s, convertible
+/// to and from the raw `u32` ID Win32 reports via `WM_COMMAND`.
+///
+/// Typically implemented by a small `enum` listing an application's menu
+/// commands.
+pub trait MenuId: Copy {
+    /// Converts to the raw ID stored in the menu.
+    fn into_raw(self) -> u32;
+
+    /// Recovers a value from a raw ID reported by `WM_COMMAND`, or `None` if
+    /// it doesn't correspond to any variant.
+    fn from_raw(raw: u32) -> Option<Self>;
+}
+
+/// A menu bar or popup menu built by [`MenuBuilder`].
+///
+/// Dropping a `Menu` that hasn't been attached to a window (via
+/// [`crate::window::Window::set_menu_bar`]) or nested into another menu
+/// (via [`MenuBuilder::submenu`]) destroys it.
+pub struct Menu {
+    menu: HMENU,
+    /// `true` while this `Menu` still owns `menu`; cleared once ownership
+    /// passes to a window or parent menu, so `Drop` doesn't destroy a
+    /// handle that is no longer ours to destroy.
+    owned: bool,
+}
+
+impl Menu {
+    /// Starts building a new, empty menu.
+    pub fn builder() -> Result<MenuBuilder> {
+        MenuBuilder::new()
+    }
+
+    pub(crate) fn handle(&self) -> HMENU {
+        self.menu
+    }
+
+    /// Marks the menu as owned by a window or parent menu, so it is no
+    /// longer destroyed on drop.
+    pub(crate) fn disown(mut self) -> HMENU {
+        self.owned = false;
+        self.menu
+    }
+}
+
+impl Drop for Menu {
+    fn drop(&mut self) {
+        if self.owned {
+            // SAFETY: `self.menu` was created by `CreateMenu`/
+            // `CreatePopupMenu` and is destroyed exactly once here.
+            unsafe {
+                let _ = DestroyMenu(self.menu);
+            }
+        }
+    }
+}
+
+/// Builds a [`Menu`], one item at a time.
+pub struct MenuBuilder {
+    menu: HMENU,
+}
+
+impl MenuBuilder {
+    fn new() -> Result<Self> {
+        // SAFETY: always valid to call.
+        let menu = unsafe { CreateMenu() }.map_err(Error::from)?;
+        Ok(Self { menu })
+    }
+
+    /// Appends a selectable item labelled `text`, reported via
+    /// [`Window::drain_menu_events`](crate::window::Window::drain_menu_events)
+    /// when chosen.
+    pub fn item(self, id: impl MenuId, text: &str) -> Result<Self> {
+        self.append_item(id, text, false, false)
+    }
+
+    /// Appends a selectable item that shows a checkmark when `checked`.
+    pub fn checkable_item(self, id: impl MenuId, text: &str, checked: bool) -> Result<Self> {
+        self.append_item(id, text, checked, false)
+    }
+
+    /// Appends a selectable item that cannot currently be chosen.
+    pub fn disabled_item(self, id: impl MenuId, text: &str) -> Result<Self> {
+        self.append_item(id, text, false, true)
+    }
+
+    fn append_item(
+        self,
+        id: impl MenuId,
+        text: &str,
+        checked: bool,
+        disabled: bool,
+    ) -> Result<Self> {
+        let mut flags = MF_STRING;
+        if checked {
+            flags = flags | MF_CHECKED;
+        } else {
+            flags = flags | MF_UNCHECKED;
+        }
+        if disabled {
+            flags = flags | MF_GRAYED;
+        }
+
+        let text = encode_wide(text);
+        // SAFETY: `self.menu` is a valid, owned menu and `text` is a
+        // null-terminated string valid for the duration of the call.
+        unsafe {
+            AppendMenuW(
+                self.menu,
+                flags,
+                id.into_raw() as usize,
+                PCWSTR(text.as_ptr()),
+            )
+        }
+        .map_err(Error::from)?;
+        Ok(self)
+    }
+
+    /// Appends a non-selectable separator line.
+    pub fn separator(self) -> Result<Self> {
+        // SAFETY: `self.menu` is a valid, owned menu.
+        unsafe { AppendMenuW(self.menu, MF_SEPARATOR, 0, PCWSTR::null()) }.map_err(Error::from)?;
+        Ok(self)
+    }
+
+    /// Appends `submenu`, labelled `text`, as a nested popup. `submenu` is
+    /// built independently, typically via [`submenu_builder`], then nested here; its
+    /// lifetime is taken over by the parent menu.
+    pub fn submenu(self, text: &str, submenu: Menu) -> Result<Self> {
+        let handle = submenu.disown();
+        let text = encode_wide(text);
+        // SAFETY: `self.menu` is a valid, owned menu and `handle` is a
+        // valid popup menu being handed off to it; `text` is a
+        // null-terminated string valid for the duration of the call.
+        unsafe {
+            AppendMenuW(
+                self.menu,
+                MF_POPUP,
+                handle.0 as usize,
+                PCWSTR(text.as_ptr()),
+            )
+        }
+        .map_err(Error::from)?;
+        Ok(self)
+    }
+
+    /// Finishes building the menu.
+    pub fn build(self) -> Menu {
+        Menu {
+            menu: self.menu,
+            owned: true,
+        }
+    }
+}
+
+/// Starts building a popup menu intended to be nested via
+/// [`MenuBuilder::submenu`] rather than attached directly to a window.
+pub fn submenu_builder() -> Result<MenuBuilder> {
+    // SAFETY: always valid to call.
+    let menu = unsafe { CreatePopupMenu() }.map_err(Error::from)?;
+    Ok(MenuBuilder { menu })
+}
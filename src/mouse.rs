@@ -0,0 +1,404 @@
+//! Mouse cursor-position and button-state tracking.
+
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+use windows::Win32::System::SystemServices::MK_SHIFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::GetDoubleClickTime;
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetMessageTime, GetSystemMetrics, SystemParametersInfoW, SM_CXDOUBLECLK, SM_CYDOUBLECLK,
+    SPI_GETWHEELSCROLLCHARS, SPI_GETWHEELSCROLLLINES, SYSTEM_PARAMETERS_INFO_ACTION,
+    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, WHEEL_DELTA, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN,
+    WM_MBUTTONUP, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_XBUTTONDOWN,
+    WM_XBUTTONUP, XBUTTON1, XBUTTON2,
+};
+
+use crate::geometry::Point2D;
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    /// The first X button, conventionally bound to "back" navigation.
+    X1,
+    /// The second X button, conventionally bound to "forward" navigation.
+    X2,
+}
+
+/// Which way a [`WheelDelta`] scrolls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WheelAxis {
+    /// From `WM_MOUSEWHEEL` without Shift held.
+    Vertical,
+    /// From `WM_MOUSEHWHEEL`, or `WM_MOUSEWHEEL` with Shift held.
+    Horizontal,
+}
+
+/// A single `WM_MOUSEWHEEL`/`WM_MOUSEHWHEEL` notification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WheelDelta {
+    /// Which way this delta scrolls.
+    pub axis: WheelAxis,
+    /// The raw delta reported by the message, in `WHEEL_DELTA` (120)
+    /// units per notch. High-resolution "free-spinning" wheels can report
+    /// less than a full notch per message, so this isn't always a
+    /// multiple of 120.
+    pub raw: i32,
+    /// `raw` converted to "lines to scroll" for [`WheelAxis::Vertical`] or
+    /// "characters to scroll" for [`WheelAxis::Horizontal`], honoring the
+    /// user's `SPI_GETWHEELSCROLLLINES`/`SPI_GETWHEELSCROLLCHARS` setting.
+    /// Fractional for free-spinning wheels; accumulate it and scroll
+    /// whenever the running total crosses a whole line for smooth
+    /// scrolling.
+    pub lines: f64,
+}
+
+/// A completed mouse button press, with Windows' standard multi-click
+/// semantics applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseClick {
+    pub button: MouseButton,
+    pub position: Point2D<i32>,
+    /// `1` for a single click, `2` for a double-click, `3` for a
+    /// triple-click, and so on, incrementing as long as consecutive
+    /// presses of the same button land close enough together in time and
+    /// space per `GetDoubleClickTime` and
+    /// `SM_CXDOUBLECLK`/`SM_CYDOUBLECLK`.
+    pub count: u32,
+}
+
+/// Tracks the last-known cursor position and button state for a window.
+#[derive(Debug, Default)]
+pub struct Mouse {
+    position: Point2D<i32>,
+    left: bool,
+    right: bool,
+    middle: bool,
+    x1: bool,
+    x2: bool,
+    last_click: Option<ClickState>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ClickState {
+    button: MouseButton,
+    position: Point2D<i32>,
+    timestamp: u32,
+    count: u32,
+}
+
+impl Mouse {
+    /// The cursor position in client coordinates, as of the last processed
+    /// `WM_MOUSEMOVE`.
+    pub fn position(&self) -> Point2D<i32> {
+        self.position
+    }
+
+    /// Returns `true` if `button` is currently held down.
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.left,
+            MouseButton::Right => self.right,
+            MouseButton::Middle => self.middle,
+            MouseButton::X1 => self.x1,
+            MouseButton::X2 => self.x2,
+        }
+    }
+
+    /// Feeds a raw window message into the mouse's state machine, returning
+    /// a [`MouseClick`] if the message was a button press.
+    pub(crate) fn process_evt(
+        &mut self,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> Option<MouseClick> {
+        match msg {
+            WM_MOUSEMOVE => {
+                self.position = point_from_lparam(lparam);
+                None
+            }
+            WM_LBUTTONDOWN => {
+                self.left = true;
+                Some(self.register_click(MouseButton::Left, point_from_lparam(lparam)))
+            }
+            WM_LBUTTONUP => {
+                self.left = false;
+                None
+            }
+            WM_RBUTTONDOWN => {
+                self.right = true;
+                Some(self.register_click(MouseButton::Right, point_from_lparam(lparam)))
+            }
+            WM_RBUTTONUP => {
+                self.right = false;
+                None
+            }
+            WM_MBUTTONDOWN => {
+                self.middle = true;
+                Some(self.register_click(MouseButton::Middle, point_from_lparam(lparam)))
+            }
+            WM_MBUTTONUP => {
+                self.middle = false;
+                None
+            }
+            WM_XBUTTONDOWN => match xbutton(wparam) {
+                Some(button @ MouseButton::X1) => {
+                    self.x1 = true;
+                    Some(self.register_click(button, point_from_lparam(lparam)))
+                }
+                Some(button @ MouseButton::X2) => {
+                    self.x2 = true;
+                    Some(self.register_click(button, point_from_lparam(lparam)))
+                }
+                _ => None,
+            },
+            WM_XBUTTONUP => match xbutton(wparam) {
+                Some(MouseButton::X1) => {
+                    self.x1 = false;
+                    None
+                }
+                Some(MouseButton::X2) => {
+                    self.x2 = false;
+                    None
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Updates the running click-count state for `button` at `position`
+    /// and returns the resulting [`MouseClick`].
+    fn register_click(&mut self, button: MouseButton, position: Point2D<i32>) -> MouseClick {
+        // SAFETY: always valid to call while processing a message.
+        let now = unsafe { GetMessageTime() } as u32;
+        // SAFETY: always valid to call.
+        let max_interval = unsafe { GetDoubleClickTime() };
+        // SAFETY: always valid to call.
+        let max_dx = unsafe { GetSystemMetrics(SM_CXDOUBLECLK) };
+        // SAFETY: always valid to call.
+        let max_dy = unsafe { GetSystemMetrics(SM_CYDOUBLECLK) };
+
+        let count = click_count(
+            self.last_click,
+            button,
+            position,
+            now,
+            max_interval,
+            max_dx,
+            max_dy,
+        );
+
+        self.last_click = Some(ClickState {
+            button,
+            position,
+            timestamp: now,
+            count,
+        });
+        MouseClick {
+            button,
+            position,
+            count,
+        }
+    }
+
+    /// Converts a `WM_MOUSEWHEEL`/`WM_MOUSEHWHEEL` message into a
+    /// [`WheelDelta`]. `WM_MOUSEHWHEEL` is always horizontal; `WM_MOUSEWHEEL`
+    /// is horizontal too if Shift is held, the conventional way to scroll
+    /// sideways with a mouse that has no tilt wheel.
+    pub(crate) fn process_wheel(msg: u32, wparam: WPARAM) -> WheelDelta {
+        let raw = ((wparam.0 as u32) >> 16) as i16 as i32;
+        let key_flags = (wparam.0 & 0xffff) as u32;
+
+        let axis = if msg == WM_MOUSEHWHEEL || key_flags & MK_SHIFT.0 != 0 {
+            WheelAxis::Horizontal
+        } else {
+            WheelAxis::Vertical
+        };
+
+        let units_per_notch = match axis {
+            WheelAxis::Vertical => scroll_setting(SPI_GETWHEELSCROLLLINES),
+            WheelAxis::Horizontal => scroll_setting(SPI_GETWHEELSCROLLCHARS),
+        };
+        let lines = raw as f64 / WHEEL_DELTA as f64 * units_per_notch as f64;
+
+        WheelDelta { axis, raw, lines }
+    }
+}
+
+/// Computes the multi-click count for a new press of `button` at
+/// `position` and time `now`, given the previous click (if any) and the
+/// current time/position thresholds. `now`/`timestamp` are compared with a
+/// wrapping subtraction since `GetMessageTime` wraps around like
+/// `GetTickCount`.
+fn click_count(
+    last_click: Option<ClickState>,
+    button: MouseButton,
+    position: Point2D<i32>,
+    now: u32,
+    max_interval: u32,
+    max_dx: i32,
+    max_dy: i32,
+) -> u32 {
+    match last_click {
+        Some(last)
+            if last.button == button
+                && now.wrapping_sub(last.timestamp) <= max_interval
+                && (position.x - last.position.x).abs() <= max_dx
+                && (position.y - last.position.y).abs() <= max_dy =>
+        {
+            last.count + 1
+        }
+        _ => 1,
+    }
+}
+
+/// Extracts the client-coordinate cursor position from a mouse message's
+/// `lParam`.
+fn point_from_lparam(lparam: LPARAM) -> Point2D<i32> {
+    let x = (lparam.0 & 0xffff) as i16 as i32;
+    let y = ((lparam.0 >> 16) & 0xffff) as i16 as i32;
+    Point2D::new(x, y)
+}
+
+/// Extracts which X button a `WM_XBUTTONDOWN`/`WM_XBUTTONUP` message is for
+/// from the high word of `wParam`, per the `GET_XBUTTON_WPARAM` macro.
+fn xbutton(wparam: WPARAM) -> Option<MouseButton> {
+    match ((wparam.0 as u32) >> 16) as u16 {
+        XBUTTON1 => Some(MouseButton::X1),
+        XBUTTON2 => Some(MouseButton::X2),
+        _ => None,
+    }
+}
+
+/// Reads a `SPI_GETWHEELSCROLLLINES`/`SPI_GETWHEELSCROLLCHARS`-style
+/// `SystemParametersInfoW` setting, falling back to the system default of
+/// 3 if it can't be read.
+fn scroll_setting(action: SYSTEM_PARAMETERS_INFO_ACTION) -> u32 {
+    let mut units = 3u32;
+    // SAFETY: `units` is a valid out-parameter of the size these actions
+    // expect for the duration of the call.
+    let result = unsafe {
+        SystemParametersInfoW(
+            action,
+            0,
+            Some(&mut units as *mut u32 as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    if result.is_ok() {
+        units
+    } else {
+        3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_INTERVAL: u32 = 500;
+    const MAX_DX: i32 = 4;
+    const MAX_DY: i32 = 4;
+
+    fn click(button: MouseButton, x: i32, y: i32, timestamp: u32, count: u32) -> ClickState {
+        ClickState {
+            button,
+            position: Point2D::new(x, y),
+            timestamp,
+            count,
+        }
+    }
+
+    #[test]
+    fn within_time_and_position_window_increments_count() {
+        let last = click(MouseButton::Left, 100, 100, 1_000, 1);
+        let count = click_count(
+            Some(last),
+            MouseButton::Left,
+            Point2D::new(102, 100),
+            1_000 + MAX_INTERVAL,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn just_outside_time_window_resets_count() {
+        let last = click(MouseButton::Left, 100, 100, 1_000, 1);
+        let count = click_count(
+            Some(last),
+            MouseButton::Left,
+            Point2D::new(100, 100),
+            1_000 + MAX_INTERVAL + 1,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn just_outside_position_window_resets_count() {
+        let last = click(MouseButton::Left, 100, 100, 1_000, 1);
+        let count = click_count(
+            Some(last),
+            MouseButton::Left,
+            Point2D::new(100 + MAX_DX + 1, 100),
+            1_000,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn time_wraparound_is_handled_correctly() {
+        // `now` has wrapped around past 0 since `last.timestamp`, but the
+        // true elapsed time is still within the window.
+        let last = click(MouseButton::Left, 100, 100, u32::MAX - 10, 2);
+        let count = click_count(
+            Some(last),
+            MouseButton::Left,
+            Point2D::new(100, 100),
+            5,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn button_swap_resets_count() {
+        let last = click(MouseButton::Left, 100, 100, 1_000, 2);
+        let count = click_count(
+            Some(last),
+            MouseButton::Right,
+            Point2D::new(100, 100),
+            1_000,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn no_previous_click_starts_at_one() {
+        let count = click_count(
+            None,
+            MouseButton::Left,
+            Point2D::new(0, 0),
+            0,
+            MAX_INTERVAL,
+            MAX_DX,
+            MAX_DY,
+        );
+        assert_eq!(count, 1);
+    }
+}
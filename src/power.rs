@@ -0,0 +1,48 @@
+//! Power and battery notifications delivered via `WM_POWERBROADCAST`, so
+//! apps can pause background work or save state before the system sleeps.
+
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+use windows::Win32::UI::WindowsAndMessaging::{
+    PBT_APMPOWERSTATUSCHANGE, PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMECRITICAL, PBT_APMRESUMESUSPEND,
+    PBT_APMSUSPEND,
+};
+
+/// A power-state change reported by `WM_POWERBROADCAST`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerEvent {
+    /// The system is about to suspend (sleep or hibernate).
+    Suspending,
+    /// The system resumed from suspend.
+    Resumed,
+    /// The AC line/battery power source changed.
+    PowerSourceChanged {
+        /// `true` if the system is now running on battery power.
+        on_battery: bool,
+    },
+}
+
+impl PowerEvent {
+    /// Maps a `WM_POWERBROADCAST` message's `wParam` to a [`PowerEvent`],
+    /// if it's one this crate surfaces. Queries the current power source
+    /// via `GetSystemPowerStatus` for `PBT_APMPOWERSTATUSCHANGE`, since
+    /// that notification itself carries no details.
+    pub(crate) fn from_wparam(wparam: WPARAM) -> Option<Self> {
+        match wparam.0 as u32 {
+            PBT_APMSUSPEND => Some(Self::Suspending),
+            PBT_APMRESUMESUSPEND | PBT_APMRESUMEAUTOMATIC | PBT_APMRESUMECRITICAL => {
+                Some(Self::Resumed)
+            }
+            PBT_APMPOWERSTATUSCHANGE => {
+                let mut status = SYSTEM_POWER_STATUS::default();
+                // SAFETY: `status` is a valid out-parameter for the
+                // duration of the call.
+                unsafe { GetSystemPowerStatus(&mut status) }.ok()?;
+                Some(Self::PowerSourceChanged {
+                    on_battery: status.ACLineStatus == 0,
+                })
+            }
+            _ => None,
+        }
+    }
+}
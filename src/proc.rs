@@ -0,0 +1,23 @@
+//! Process-wide identity metadata consumed by Explorer for taskbar
+//! grouping, jump lists, and toast notification routing.
+
+use windows::core::PCWSTR;
+use windows::Win32::UI::Shell::SetCurrentProcessExplicitAppUserModelID;
+
+use crate::error::{Error, Result};
+use crate::util::encode_wide;
+
+/// Sets the calling process's AppUserModelID, via
+/// `SetCurrentProcessExplicitAppUserModelID`.
+///
+/// Explorer uses the AppUserModelID to group a process's windows under one
+/// taskbar button, to route toast notifications back to it, and to
+/// associate it with a jump list. Must be called once, before creating any
+/// windows; per-window overrides are available via
+/// [`crate::window::Window::set_app_user_model_id`].
+pub fn set_app_user_model_id(id: &str) -> Result<()> {
+    let encoded = encode_wide(id);
+    // SAFETY: `encoded` outlives the call and is null-terminated.
+    unsafe { SetCurrentProcessExplicitAppUserModelID(PCWSTR(encoded.as_ptr())) }
+        .map_err(Error::from)
+}
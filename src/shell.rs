@@ -0,0 +1,617 @@
+//! System tray (notification area) icons via `Shell_NotifyIconW`, backed by
+//! a hidden message-only window that receives their callback messages, plus
+//! standalone helpers ([`open_url`], [`open_path`], [`reveal_in_explorer`])
+//! for launching other shell UI on the user's behalf.
+
+#[cfg(feature = "toast")]
+pub mod toast;
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{ERROR_BAD_FORMAT, HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows::Win32::Graphics::Gdi::{CreateBitmap, DeleteObject};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+#[cfg(feature = "shell_execute")]
+use windows::Win32::UI::Shell::{ILCreateFromPathW, ILFree, SHOpenFolderAndSelectItems};
+use windows::Win32::UI::Shell::{
+    ShellExecuteW, Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_ERROR,
+    NIIF_INFO, NIIF_NONE, NIIF_WARNING, NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
+    NOTIFY_ICON_INFOTIP_FLAGS, NOTIFY_ICON_MESSAGE,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreateIconFromResourceEx, CreateIconIndirect, CreatePopupMenu, CreateWindowExW,
+    DefWindowProcW, DestroyIcon, DestroyMenu, DestroyWindow, GetCursorPos, GetWindowLongPtrW,
+    PostMessageW, RegisterClassExW, SetForegroundWindow, SetWindowLongPtrW, TrackPopupMenuEx,
+    CREATESTRUCTW, CW_USEDEFAULT, GWLP_USERDATA, HICON, HMENU, HWND_MESSAGE, ICONINFO,
+    LR_DEFAULTCOLOR, LR_DEFAULTSIZE, MF_SEPARATOR, MF_STRING, SW_SHOWNORMAL, TPM_BOTTOMALIGN,
+    TPM_LEFTALIGN, TPM_RIGHTBUTTON, WM_COMMAND, WM_LBUTTONDBLCLK, WM_LBUTTONUP, WM_NCCREATE,
+    WM_NULL, WM_RBUTTONUP, WM_USER, WNDCLASSEXW,
+};
+
+use crate::dib::rgba_to_bgra_dib;
+use crate::error::{Error, Result};
+use crate::util::encode_wide;
+
+/// The class name shared by every tray icon's hidden message-only window.
+const CLASS_NAME: PCWSTR = w!("Skylight::TrayIcon");
+
+/// The window message `Shell_NotifyIconW` uses to report mouse activity on
+/// the icon, chosen arbitrarily above `WM_USER`.
+const WM_TRAYICON: u32 = WM_USER + 1;
+
+/// A mouse interaction with a [`TrayIcon`], reported by
+/// [`TrayIcon::drain_events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon was left-clicked.
+    Click,
+    /// The icon was left-double-clicked.
+    DoubleClick,
+    /// The icon was right-clicked, and no context menu was set via
+    /// [`TrayIcon::set_context_menu`] to handle it automatically.
+    RightClick,
+    /// The item with the given ID was selected from the context menu set
+    /// via [`TrayIcon::set_context_menu`].
+    MenuItemSelected(u32),
+}
+
+/// The icon shown alongside a [`TrayIcon::show_notification`] balloon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotificationIcon {
+    /// No icon.
+    #[default]
+    None,
+    /// The system information icon.
+    Info,
+    /// The system warning icon.
+    Warning,
+    /// The system error icon.
+    Error,
+}
+
+impl NotificationIcon {
+    fn to_niif(self) -> NOTIFY_ICON_INFOTIP_FLAGS {
+        match self {
+            Self::None => NIIF_NONE,
+            Self::Info => NIIF_INFO,
+            Self::Warning => NIIF_WARNING,
+            Self::Error => NIIF_ERROR,
+        }
+    }
+}
+
+/// A system tray (notification area) icon.
+///
+/// Dropping a `TrayIcon` removes it from the notification area and
+/// destroys its hidden window.
+pub struct TrayIcon {
+    hwnd: HWND,
+    id: u32,
+    inner: Box<TrayIconInner>,
+}
+
+/// Per-icon state addressed via the hidden window's `GWLP_USERDATA` slot.
+#[derive(Default)]
+struct TrayIconInner {
+    events: RefCell<VecDeque<TrayEvent>>,
+    /// The context menu shown on right-click, set via
+    /// [`TrayIcon::set_context_menu`].
+    menu: RefCell<Option<TrayMenu>>,
+}
+
+impl TrayIcon {
+    /// Adds a new tray icon showing `icon`, with tooltip text `tooltip`.
+    pub fn new(icon: &Icon, tooltip: &str) -> Result<Self> {
+        register_class();
+
+        let inner = Box::new(TrayIconInner::default());
+        let inner_ptr = Box::into_raw(inner);
+
+        // SAFETY: `inner_ptr` is a uniquely-owned pointer handed to the
+        // window procedure via `lpParam`; it is reclaimed into a `Box`
+        // exactly once below, whether creation succeeds or fails.
+        let hwnd = unsafe {
+            CreateWindowExW(
+                Default::default(),
+                CLASS_NAME,
+                PCWSTR::null(),
+                Default::default(),
+                0,
+                0,
+                CW_USEDEFAULT,
+                CW_USEDEFAULT,
+                Some(HWND_MESSAGE),
+                None,
+                None,
+                Some(inner_ptr.cast()),
+            )
+        };
+
+        let hwnd = match hwnd {
+            Ok(hwnd) => hwnd,
+            Err(err) => {
+                // SAFETY: creation failed before the window procedure could
+                // observe `inner_ptr`, so we still hold sole ownership.
+                unsafe {
+                    drop(Box::from_raw(inner_ptr));
+                }
+                return Err(Error::from(err));
+            }
+        };
+
+        // SAFETY: `inner_ptr` was stashed in `GWLP_USERDATA` during
+        // `WM_NCCREATE` and is reclaimed here exactly once.
+        let inner = unsafe { Box::from_raw(inner_ptr) };
+
+        let id = 1;
+        let mut data = notify_icon_data(hwnd, id);
+        data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+        data.uCallbackMessage = WM_TRAYICON;
+        data.hIcon = icon.handle();
+        set_tip(&mut data, tooltip);
+
+        // SAFETY: `data` is fully initialized and describes `hwnd`, which
+        // was just created above.
+        if let Err(err) = unsafe { Shell_NotifyIconW(NIM_ADD, &data) }.ok() {
+            // SAFETY: creation of the tray icon failed, so `hwnd` never
+            // gained a `Shell_NotifyIconW` entry to clean up.
+            unsafe {
+                let _ = DestroyWindow(hwnd);
+            }
+            return Err(Error::from(err));
+        }
+
+        Ok(Self { hwnd, id, inner })
+    }
+
+    /// Replaces the icon shown in the notification area.
+    pub fn set_icon(&self, icon: &Icon) -> Result<()> {
+        let mut data = notify_icon_data(self.hwnd, self.id);
+        data.uFlags = NIF_ICON;
+        data.hIcon = icon.handle();
+        self.notify(NIM_MODIFY, &data)
+    }
+
+    /// Replaces the tooltip text shown while hovering over the icon.
+    pub fn set_tooltip(&self, tooltip: &str) -> Result<()> {
+        let mut data = notify_icon_data(self.hwnd, self.id);
+        data.uFlags = NIF_TIP;
+        set_tip(&mut data, tooltip);
+        self.notify(NIM_MODIFY, &data)
+    }
+
+    /// Removes and returns all mouse events accumulated since the last
+    /// call.
+    pub fn drain_events(&self) -> Vec<TrayEvent> {
+        self.inner.events.borrow_mut().drain(..).collect()
+    }
+
+    /// Sets the context menu shown on right-click, replacing any
+    /// previously set menu. Selections are reported via
+    /// [`TrayEvent::MenuItemSelected`] rather than [`TrayEvent::RightClick`]
+    /// while a menu is set.
+    pub fn set_context_menu(&self, menu: TrayMenu) {
+        *self.inner.menu.borrow_mut() = Some(menu);
+    }
+
+    /// Shows a balloon notification anchored to the icon.
+    pub fn show_notification(&self, title: &str, body: &str, icon: NotificationIcon) -> Result<()> {
+        let mut data = notify_icon_data(self.hwnd, self.id);
+        data.uFlags = NIF_INFO;
+        data.dwInfoFlags = icon.to_niif();
+        copy_to_buf(&mut data.szInfoTitle, title);
+        copy_to_buf(&mut data.szInfo, body);
+        self.notify(NIM_MODIFY, &data)
+    }
+
+    fn notify(&self, message: NOTIFY_ICON_MESSAGE, data: &NOTIFYICONDATAW) -> Result<()> {
+        // SAFETY: `data` is fully initialized and describes `self.hwnd`,
+        // which is live for the lifetime of `self`.
+        unsafe { Shell_NotifyIconW(message, data) }
+            .ok()
+            .map_err(Error::from)
+    }
+}
+
+impl Drop for TrayIcon {
+    fn drop(&mut self) {
+        let data = notify_icon_data(self.hwnd, self.id);
+        // SAFETY: `data` identifies the icon added in `TrayIcon::new`.
+        unsafe {
+            let _ = Shell_NotifyIconW(NIM_DELETE, &data);
+        }
+        // SAFETY: `self.hwnd` was created in `TrayIcon::new` and is
+        // destroyed exactly once here.
+        unsafe {
+            let _ = DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+/// Builds a zeroed `NOTIFYICONDATAW` identifying `hwnd`/`id`, ready for the
+/// caller to set `uFlags` and the fields they describe.
+fn notify_icon_data(hwnd: HWND, id: u32) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: id,
+        ..Default::default()
+    }
+}
+
+/// Copies `tip` into `data.szTip`, truncating to fit its fixed-size buffer.
+fn set_tip(data: &mut NOTIFYICONDATAW, tip: &str) {
+    copy_to_buf(&mut data.szTip, tip);
+}
+
+/// Copies `text` into `buf`, truncating to fit and leaving room for the
+/// trailing null terminator.
+fn copy_to_buf(buf: &mut [u16], text: &str) {
+    let text: Vec<u16> = text.encode_utf16().collect();
+    let len = text.len().min(buf.len() - 1);
+    buf[..len].copy_from_slice(&text[..len]);
+    buf[len] = 0;
+}
+
+/// Registers the hidden window class shared by every tray icon, the first
+/// time one is created.
+fn register_class() {
+    static REGISTERED: OnceLock<()> = OnceLock::new();
+    REGISTERED.get_or_init(|| {
+        // SAFETY: `GetModuleHandleW(None)` returns a handle to the current
+        // module, which is always valid for the lifetime of the process.
+        let instance = unsafe { GetModuleHandleW(None) }.unwrap_or_default();
+
+        let class = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            lpfnWndProc: Some(wnd_proc),
+            hInstance: instance.into(),
+            lpszClassName: CLASS_NAME,
+            ..Default::default()
+        };
+
+        // SAFETY: `class` is fully initialized; `lpfnWndProc` points to a
+        // `'static` function, so the registration is valid for the
+        // lifetime of the process.
+        unsafe {
+            RegisterClassExW(&class);
+        }
+    });
+}
+
+/// Recovers the [`TrayIconInner`] stashed in `hwnd`'s `GWLP_USERDATA` slot,
+/// if any (it is absent for messages dispatched before `WM_NCCREATE`).
+unsafe fn inner_for(hwnd: HWND) -> Option<*const TrayIconInner> {
+    let ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const TrayIconInner;
+    if ptr.is_null() {
+        None
+    } else {
+        Some(ptr)
+    }
+}
+
+/// The window procedure for every tray icon's hidden message-only window.
+unsafe extern "system" fn wnd_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_NCCREATE {
+        let create_struct = &*(lparam.0 as *const CREATESTRUCTW);
+        SetWindowLongPtrW(hwnd, GWLP_USERDATA, create_struct.lpCreateParams as isize);
+    }
+
+    if msg == WM_TRAYICON {
+        if let Some(inner) = inner_for(hwnd) {
+            match lparam.0 as u32 {
+                WM_LBUTTONUP => (*inner).events.borrow_mut().push_back(TrayEvent::Click),
+                WM_LBUTTONDBLCLK => (*inner)
+                    .events
+                    .borrow_mut()
+                    .push_back(TrayEvent::DoubleClick),
+                WM_RBUTTONUP => {
+                    if let Some(menu) = (*inner).menu.borrow().as_ref() {
+                        show_context_menu(hwnd, menu.handle());
+                    } else {
+                        (*inner)
+                            .events
+                            .borrow_mut()
+                            .push_back(TrayEvent::RightClick);
+                    }
+                }
+                _ => {}
+            }
+        }
+        return LRESULT(0);
+    }
+
+    if msg == WM_COMMAND {
+        if let Some(inner) = inner_for(hwnd) {
+            let id = (wparam.0 & 0xffff) as u32;
+            (*inner)
+                .events
+                .borrow_mut()
+                .push_back(TrayEvent::MenuItemSelected(id));
+        }
+        return LRESULT(0);
+    }
+
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+/// Pops up `menu` at the current cursor position, owned by `hwnd`, which
+/// receives the resulting `WM_COMMAND` if an item is selected.
+fn show_context_menu(hwnd: HWND, menu: HMENU) {
+    let mut point = POINT::default();
+    // SAFETY: `point` is a valid out-parameter for the duration of the
+    // call.
+    unsafe {
+        let _ = GetCursorPos(&mut point);
+    }
+
+    // Required so the menu closes properly if the user clicks elsewhere,
+    // since `hwnd` is a hidden message-only window rather than the
+    // foreground window.
+    // SAFETY: `hwnd` is a valid, live window.
+    unsafe {
+        let _ = SetForegroundWindow(hwnd);
+    }
+
+    // SAFETY: `menu` and `hwnd` are both valid and live for the duration of
+    // the call, which blocks until the menu is dismissed.
+    unsafe {
+        let _ = TrackPopupMenuEx(
+            menu,
+            (TPM_RIGHTBUTTON | TPM_LEFTALIGN | TPM_BOTTOMALIGN).0,
+            point.x,
+            point.y,
+            hwnd,
+            None,
+        );
+    }
+
+    // Works around a long-standing Explorer bug where the next click is
+    // swallowed unless the owner window receives a harmless message right
+    // after the menu closes.
+    // SAFETY: `hwnd` is a valid, live window.
+    unsafe {
+        let _ = PostMessageW(Some(hwnd), WM_NULL, WPARAM(0), LPARAM(0));
+    }
+}
+
+/// A context menu shown by [`TrayIcon::set_context_menu`] on right-click.
+pub struct TrayMenu {
+    menu: HMENU,
+}
+
+impl TrayMenu {
+    /// Creates an empty popup menu.
+    pub fn new() -> Result<Self> {
+        // SAFETY: always valid to call.
+        let menu = unsafe { CreatePopupMenu() }.map_err(Error::from)?;
+        Ok(Self { menu })
+    }
+
+    /// Appends a selectable item labelled `text`, reported via
+    /// [`TrayEvent::MenuItemSelected`] with `id` when chosen.
+    pub fn append_item(&mut self, id: u32, text: &str) -> Result<()> {
+        let text = encode_wide(text);
+        // SAFETY: `self.menu` is a valid, owned popup menu and `text` is a
+        // null-terminated string valid for the duration of the call.
+        unsafe { AppendMenuW(self.menu, MF_STRING, id as usize, PCWSTR(text.as_ptr())) }
+            .map_err(Error::from)
+    }
+
+    /// Appends a non-selectable separator line.
+    pub fn append_separator(&mut self) -> Result<()> {
+        // SAFETY: `self.menu` is a valid, owned popup menu.
+        unsafe { AppendMenuW(self.menu, MF_SEPARATOR, 0, PCWSTR::null()) }.map_err(Error::from)
+    }
+
+    fn handle(&self) -> HMENU {
+        self.menu
+    }
+}
+
+impl Drop for TrayMenu {
+    fn drop(&mut self) {
+        // SAFETY: `self.menu` was created by `CreatePopupMenu` and is
+        // destroyed exactly once here.
+        unsafe {
+            let _ = DestroyMenu(self.menu);
+        }
+    }
+}
+
+/// An icon image, usable as a [`TrayIcon`]'s icon.
+pub struct Icon {
+    icon: HICON,
+}
+
+impl Icon {
+    /// Builds an icon from `width` x `height` straight-alpha RGBA8 pixel
+    /// data, row-major top-to-bottom.
+    ///
+    /// Panics if `pixels` is shorter than `width * height * 4` bytes.
+    pub fn from_rgba(width: i32, height: i32, pixels: &[u8]) -> Result<Self> {
+        let (color, bits) = rgba_to_bgra_dib(width, height, pixels.len())?;
+
+        // SAFETY: `bits` was sized by `rgba_to_bgra_dib` above for exactly
+        // `width * height` 32bpp pixels.
+        let dst = unsafe {
+            std::slice::from_raw_parts_mut(bits, (width as usize) * (height as usize) * 4)
+        };
+        for (src, dst) in pixels.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+            // RGBA -> BGRA, matching the DIB's pixel layout.
+            dst[0] = src[2];
+            dst[1] = src[1];
+            dst[2] = src[0];
+            dst[3] = src[3];
+        }
+
+        // An icon's AND mask is ignored once its color bitmap carries an
+        // alpha channel, so its content doesn't matter, only its
+        // dimensions.
+        // SAFETY: `None` requests a zero-initialized bitmap of this size.
+        let mask = unsafe { CreateBitmap(width, height, 1, 1, None) };
+
+        let icon_info = ICONINFO {
+            fIcon: true.into(),
+            xHotspot: 0,
+            yHotspot: 0,
+            hbmMask: mask,
+            hbmColor: color,
+        };
+        // SAFETY: `icon_info` is fully initialized and both bitmaps match
+        // its declared dimensions.
+        let icon = unsafe { CreateIconIndirect(&icon_info) };
+
+        // SAFETY: `CreateIconIndirect` duplicates both bitmaps into GDI
+        // objects it owns, so the originals must still be freed here.
+        unsafe {
+            let _ = DeleteObject(color.into());
+            let _ = DeleteObject(mask.into());
+        }
+
+        Ok(Self {
+            icon: icon.map_err(Error::from)?,
+        })
+    }
+
+    /// Builds an icon by decoding the largest image in an in-memory `.ico`
+    /// file's directory, via `CreateIconFromResourceEx`.
+    ///
+    /// Returns `Err` if `bytes` isn't a well-formed `.ico` file.
+    pub fn from_ico_bytes(bytes: &[u8]) -> Result<Self> {
+        let range = largest_ico_entry(bytes).ok_or_else(invalid_ico_error)?;
+        let data = bytes.get(range).ok_or_else(invalid_ico_error)?;
+
+        // SAFETY: `data` is one image's bytes from the `.ico` file's
+        // directory, exactly what `CreateIconFromResourceEx` expects.
+        let icon = unsafe {
+            CreateIconFromResourceEx(
+                data,
+                true,
+                0x0003_0000,
+                0,
+                0,
+                LR_DEFAULTCOLOR | LR_DEFAULTSIZE,
+            )
+        }
+        .map_err(Error::from)?;
+
+        Ok(Self { icon })
+    }
+
+    /// Builds an icon by reading and decoding an on-disk `.ico` file. See
+    /// [`Icon::from_ico_bytes`].
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_ico_bytes(&bytes)
+    }
+
+    /// The underlying icon handle.
+    pub(crate) fn handle(&self) -> HICON {
+        self.icon
+    }
+}
+
+/// Finds the byte range of the largest image in an in-memory `.ico` file's
+/// `ICONDIR`/`ICONDIRENTRY` directory, for [`Icon::from_ico_bytes`].
+fn largest_ico_entry(bytes: &[u8]) -> Option<std::ops::Range<usize>> {
+    let count = u16::from_le_bytes(bytes.get(4..6)?.try_into().ok()?) as usize;
+    (0..count)
+        .filter_map(|i| {
+            let entry = bytes.get(6 + i * 16..6 + (i + 1) * 16)?;
+            let width = if entry[0] == 0 { 256 } else { entry[0] as u32 };
+            let height = if entry[1] == 0 { 256 } else { entry[1] as u32 };
+            let size = u32::from_le_bytes(entry[8..12].try_into().ok()?) as usize;
+            let offset = u32::from_le_bytes(entry[12..16].try_into().ok()?) as usize;
+            Some((width * height, offset..offset.checked_add(size)?))
+        })
+        .max_by_key(|(area, _)| *area)
+        .map(|(_, range)| range)
+}
+
+/// The error returned for a malformed `.ico` file.
+fn invalid_ico_error() -> Error {
+    Error::from(windows::core::Error::from(
+        windows::core::HRESULT::from_win32(ERROR_BAD_FORMAT.0),
+    ))
+}
+
+impl Drop for Icon {
+    fn drop(&mut self) {
+        // SAFETY: `self.icon` was created by `CreateIconIndirect` and is
+        // destroyed exactly once here.
+        unsafe {
+            let _ = DestroyIcon(self.icon);
+        }
+    }
+}
+
+/// Opens `url` in the user's default browser, via `ShellExecuteW`'s `"open"`
+/// verb.
+pub fn open_url(url: &str) -> Result<()> {
+    shell_execute(&encode_wide(url))
+}
+
+/// Opens `path` with its default associated application, via
+/// `ShellExecuteW`'s `"open"` verb.
+pub fn open_path(path: &Path) -> Result<()> {
+    shell_execute(&encode_wide(&path.to_string_lossy()))
+}
+
+/// Runs `ShellExecuteW` with the `"open"` verb on an already-encoded,
+/// null-terminated UTF-16 target.
+fn shell_execute(target: &[u16]) -> Result<()> {
+    // SAFETY: `target` outlives the call and is null-terminated; the
+    // remaining parameters are all `None`/null, which `ShellExecuteW`
+    // accepts.
+    let result = unsafe {
+        ShellExecuteW(
+            None,
+            PCWSTR::null(),
+            PCWSTR(target.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // `ShellExecuteW` returns a pseudo-`HINSTANCE`; values greater than 32
+    // indicate success, anything else is an error code.
+    if (result.0 as isize) > 32 {
+        Ok(())
+    } else {
+        Err(Error::from(windows::core::Error::from_win32()))
+    }
+}
+
+/// Opens a File Explorer window with `path` selected, via
+/// `SHOpenFolderAndSelectItems`.
+#[cfg(feature = "shell_execute")]
+pub fn reveal_in_explorer(path: &Path) -> Result<()> {
+    let encoded = encode_wide(&path.to_string_lossy());
+
+    // SAFETY: `encoded` is a valid null-terminated UTF-16 string for the
+    // duration of the call.
+    let pidl = unsafe { ILCreateFromPathW(PCWSTR(encoded.as_ptr())) };
+    if pidl.is_null() {
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+
+    // SAFETY: `pidl` was just allocated by `ILCreateFromPathW` above and is
+    // freed exactly once here, after `SHOpenFolderAndSelectItems` has
+    // finished with it.
+    let result =
+        unsafe { SHOpenFolderAndSelectItems(pidl as *const _, Some(&[pidl as *const _]), 0) };
+    unsafe { ILFree(Some(pidl)) };
+
+    result.map_err(Error::from)
+}
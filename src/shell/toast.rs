@@ -0,0 +1,131 @@
+//! Toast notifications via the WinRT `ToastNotificationManager`, delivered
+//! under whichever AppUserModelID the app registered through
+//! [`crate::proc::set_app_user_model_id`].
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use windows::core::{Interface, HSTRING};
+use windows::Data::Xml::Dom::XmlDocument;
+use windows::Foundation::TypedEventHandler;
+use windows::UI::Notifications::{
+    ToastActivatedEventArgs, ToastNotification, ToastNotificationManager,
+};
+
+use crate::error::{Error, Result};
+
+/// A button shown on a [`Toast`], whose `id` is reported back through the
+/// toast's activation channel when clicked.
+#[derive(Debug, Clone)]
+pub struct ToastButton {
+    /// Identifies which button was clicked, echoed back in
+    /// [`ToastActivation::button_id`].
+    pub id: String,
+    /// The button's visible label.
+    pub label: String,
+}
+
+/// Reports that a [`Toast`] was activated, either by clicking its body or
+/// one of its buttons.
+#[derive(Debug, Clone)]
+pub struct ToastActivation {
+    /// The id of the [`ToastButton`] that was clicked, or `None` if the
+    /// toast's body itself was clicked.
+    pub button_id: Option<String>,
+}
+
+/// A toast notification shown via the WinRT `ToastNotificationManager`.
+///
+/// Dropping a `Toast` stops delivering activations through the channel
+/// returned by [`Toast::show`]; it does not dismiss an already-visible
+/// notification.
+pub struct Toast {
+    notification: ToastNotification,
+    activated_token: i64,
+}
+
+impl Toast {
+    /// Builds and shows a toast with `title`/`body` text and up to five
+    /// `buttons`, returning it alongside a channel that reports
+    /// activations for as long as the returned `Toast` stays alive.
+    pub fn show(
+        title: &str,
+        body: &str,
+        buttons: &[ToastButton],
+    ) -> Result<(Self, Receiver<ToastActivation>)> {
+        let document = XmlDocument::new().map_err(Error::from)?;
+        document
+            .LoadXml(&HSTRING::from(toast_xml(title, body, buttons)))
+            .map_err(Error::from)?;
+        let notification =
+            ToastNotification::CreateToastNotification(&document).map_err(Error::from)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let activated_token = notification
+            .Activated(&TypedEventHandler::new(move |_, args| {
+                send_activation(&sender, args);
+                Ok(())
+            }))
+            .map_err(Error::from)?;
+
+        ToastNotificationManager::CreateToastNotifier()
+            .and_then(|notifier| notifier.Show(&notification))
+            .map_err(Error::from)?;
+
+        Ok((
+            Self {
+                notification,
+                activated_token,
+            },
+            receiver,
+        ))
+    }
+}
+
+impl Drop for Toast {
+    fn drop(&mut self) {
+        let _ = self.notification.RemoveActivated(self.activated_token);
+    }
+}
+
+/// Reports an `Activated` event through `sender`, extracting the clicked
+/// button's id from `args`'s `arguments` string, if any.
+fn send_activation(
+    sender: &Sender<ToastActivation>,
+    args: windows::core::Ref<'_, windows::core::IInspectable>,
+) {
+    let button_id = args
+        .as_ref()
+        .and_then(|args| args.cast::<ToastActivatedEventArgs>().ok())
+        .and_then(|args| args.Arguments().ok())
+        .map(|arguments| arguments.to_string_lossy())
+        .filter(|arguments| !arguments.is_empty());
+
+    let _ = sender.send(ToastActivation { button_id });
+}
+
+/// Renders `title`/`body`/`buttons` as the `ToastGeneric` notification XML
+/// schema Explorer expects.
+fn toast_xml(title: &str, body: &str, buttons: &[ToastButton]) -> String {
+    let mut actions = String::new();
+    for button in buttons {
+        actions.push_str(&format!(
+            "<action content=\"{}\" arguments=\"{}\" />",
+            escape(&button.label),
+            escape(&button.id),
+        ));
+    }
+
+    format!(
+        "<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual><actions>{actions}</actions></toast>",
+        escape(title),
+        escape(body),
+    )
+}
+
+/// Escapes `text` for use in an XML text node or attribute value.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,181 @@
+//! Keyboard shortcut/chord matching on top of [`Keyboard`], so applications
+//! don't need to re-implement modifier bookkeeping themselves.
+
+use std::collections::HashSet;
+
+use crate::keyboard::{KeyCode, Keyboard};
+
+/// A key combination, e.g. Ctrl+Shift+P.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    key: KeyCode,
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl Shortcut {
+    /// A shortcut triggered by `key` alone, with no modifiers.
+    pub fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    /// Requires Ctrl to be held alongside the key.
+    pub fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Requires Shift to be held alongside the key.
+    pub fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Requires Alt to be held alongside the key.
+    pub fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Returns `true` if `keyboard`'s currently-held modifiers exactly
+    /// match those required by this shortcut.
+    fn modifiers_match(&self, keyboard: &Keyboard) -> bool {
+        self.ctrl == keyboard.is_pressed(KeyCode::Control)
+            && self.shift == keyboard.is_pressed(KeyCode::Shift)
+            && self.alt == keyboard.is_pressed(KeyCode::Alt)
+    }
+}
+
+/// Maps [`Shortcut`]s to application-defined actions, reporting the actions
+/// triggered since the last call to [`ShortcutMap::update`].
+///
+/// A shortcut triggers once when its key transitions from released to
+/// pressed while its required modifiers are held, and does not re-trigger
+/// until the key is released and pressed again, even if held down long
+/// enough to auto-repeat.
+#[derive(Debug, Default)]
+pub struct ShortcutMap<Action> {
+    bindings: Vec<(Shortcut, Action)>,
+    pressed: HashSet<KeyCode>,
+}
+
+impl<Action: Copy> ShortcutMap<Action> {
+    /// Creates an empty shortcut map.
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pressed: HashSet::new(),
+        }
+    }
+
+    /// Binds `shortcut` to `action`, replacing any existing binding for the
+    /// same shortcut.
+    pub fn bind(&mut self, shortcut: Shortcut, action: Action) {
+        self.bindings.retain(|(bound, _)| *bound != shortcut);
+        self.bindings.push((shortcut, action));
+    }
+
+    /// Removes any binding for `shortcut`.
+    pub fn unbind(&mut self, shortcut: Shortcut) {
+        self.bindings.retain(|(bound, _)| *bound != shortcut);
+    }
+
+    /// Checks `keyboard`'s current state against every binding, returning
+    /// the actions newly triggered since the last call. Intended to be
+    /// called once per frame.
+    pub fn update(&mut self, keyboard: &Keyboard) -> Vec<Action> {
+        let mut triggered = Vec::new();
+        let mut now_pressed = HashSet::new();
+
+        for (shortcut, action) in &self.bindings {
+            if !keyboard.is_pressed(shortcut.key) {
+                continue;
+            }
+            now_pressed.insert(shortcut.key);
+
+            if self.pressed.contains(&shortcut.key) {
+                continue;
+            }
+            if shortcut.modifiers_match(keyboard) {
+                triggered.push(*action);
+            }
+        }
+
+        self.pressed = now_pressed;
+        triggered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use windows::Win32::Foundation::{LPARAM, WPARAM};
+    use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+    use windows::Win32::UI::WindowsAndMessaging::{WM_KEYDOWN, WM_KEYUP};
+
+    use super::*;
+
+    fn press(keyboard: &mut Keyboard, code: KeyCode) {
+        keyboard.process_evt(
+            WM_KEYDOWN,
+            WPARAM(VIRTUAL_KEY::from(code).0 as usize),
+            LPARAM(0),
+        );
+    }
+
+    fn release(keyboard: &mut Keyboard, code: KeyCode) {
+        // `was_down` is always set for `WM_KEYUP`.
+        keyboard.process_evt(
+            WM_KEYUP,
+            WPARAM(VIRTUAL_KEY::from(code).0 as usize),
+            LPARAM(1 << 30),
+        );
+    }
+
+    #[test]
+    fn triggers_once_per_press_not_while_held() {
+        let mut keyboard = Keyboard::default();
+        let mut map = ShortcutMap::new();
+        map.bind(Shortcut::new(KeyCode::A), 1);
+
+        press(&mut keyboard, KeyCode::A);
+        assert_eq!(map.update(&keyboard), vec![1]);
+        // Still held, with no new message in between: must not retrigger.
+        assert_eq!(map.update(&keyboard), Vec::<i32>::new());
+        assert_eq!(map.update(&keyboard), Vec::<i32>::new());
+
+        release(&mut keyboard, KeyCode::A);
+        assert_eq!(map.update(&keyboard), Vec::<i32>::new());
+
+        press(&mut keyboard, KeyCode::A);
+        assert_eq!(map.update(&keyboard), vec![1]);
+    }
+
+    #[test]
+    fn requires_exact_modifier_match() {
+        let mut keyboard = Keyboard::default();
+        let mut map = ShortcutMap::new();
+        map.bind(Shortcut::new(KeyCode::P).with_ctrl(), 1);
+
+        press(&mut keyboard, KeyCode::Control);
+        press(&mut keyboard, KeyCode::Shift);
+        press(&mut keyboard, KeyCode::P);
+        // Ctrl+Shift+P: Shift isn't required by the binding, so this isn't
+        // an exact match.
+        assert_eq!(map.update(&keyboard), Vec::<i32>::new());
+
+        release(&mut keyboard, KeyCode::P);
+        map.update(&keyboard);
+        release(&mut keyboard, KeyCode::Shift);
+        map.update(&keyboard);
+
+        // Ctrl+P alone is an exact match.
+        press(&mut keyboard, KeyCode::P);
+        assert_eq!(map.update(&keyboard), vec![1]);
+    }
+}
@@ -0,0 +1,316 @@
+//! A `TaskDialogIndirect` builder, feature-gated behind `task_dialog`,
+//! giving skylight apps modern-looking dialogs with custom buttons, radio
+//! options, a verification checkbox, hyperlinks, and a simple progress
+//! bar, via a callback bridge rather than requiring callers to write their
+//! own `PFTASKDIALOGCALLBACK`.
+
+use windows::core::{HRESULT, PCWSTR};
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::UI::Controls::{
+    TaskDialogIndirect, TASKDIALOGCONFIG, TASKDIALOG_BUTTON, TASKDIALOG_NOTIFICATIONS,
+    TDF_ALLOW_DIALOG_CANCELLATION, TDF_ENABLE_HYPERLINKS, TDF_SHOW_PROGRESS_BAR,
+    TDF_VERIFICATION_FLAG_CHECKED, TDM_SET_PROGRESS_BAR_POS, TDN_CREATED, TDN_HYPERLINK_CLICKED,
+    TD_ERROR_ICON, TD_INFORMATION_ICON, TD_WARNING_ICON,
+};
+use windows::Win32::UI::WindowsAndMessaging::SendMessageW;
+
+use crate::error::{Error, Result};
+use crate::util::encode_wide;
+use crate::window::Window;
+
+/// The icon shown in a [`TaskDialog`]'s main instruction area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskDialogIcon {
+    /// No icon.
+    #[default]
+    None,
+    /// The "i" information icon.
+    Information,
+    /// The triangular "!" warning icon.
+    Warning,
+    /// The "x" error icon.
+    Error,
+}
+
+impl TaskDialogIcon {
+    fn pcwstr(self) -> PCWSTR {
+        match self {
+            Self::None => PCWSTR::null(),
+            Self::Information => TD_INFORMATION_ICON,
+            Self::Warning => TD_WARNING_ICON,
+            Self::Error => TD_ERROR_ICON,
+        }
+    }
+}
+
+/// The outcome of showing a [`TaskDialog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskDialogResult {
+    /// The ID of the button the user chose (a caller-supplied custom ID
+    /// from [`TaskDialog::with_button`], or one of the well-known
+    /// `IDOK`/`IDCANCEL`/... values if none were supplied).
+    pub button: i32,
+    /// The ID of the radio button the user chose, if any were offered via
+    /// [`TaskDialog::with_radio_button`].
+    pub radio_button: Option<i32>,
+    /// Whether the verification checkbox was checked, if one was shown via
+    /// [`TaskDialog::with_verification_text`].
+    pub verification_checked: bool,
+}
+
+/// Builds and shows a `TaskDialogIndirect` dialog.
+pub struct TaskDialog {
+    title: String,
+    main_instruction: String,
+    content: String,
+    icon: TaskDialogIcon,
+    allow_cancellation: bool,
+    buttons: Vec<(i32, String)>,
+    radio_buttons: Vec<(i32, String)>,
+    default_radio_button: Option<i32>,
+    verification_text: Option<(String, bool)>,
+    hyperlinks_enabled: bool,
+    progress_percent: Option<u16>,
+    on_hyperlink: Option<Box<dyn FnMut(&str)>>,
+}
+
+impl TaskDialog {
+    /// Starts building a new task dialog with the given main instruction.
+    pub fn new(main_instruction: impl Into<String>) -> Self {
+        Self {
+            title: String::new(),
+            main_instruction: main_instruction.into(),
+            content: String::new(),
+            icon: TaskDialogIcon::None,
+            allow_cancellation: true,
+            buttons: Vec::new(),
+            radio_buttons: Vec::new(),
+            default_radio_button: None,
+            verification_text: None,
+            hyperlinks_enabled: false,
+            progress_percent: None,
+            on_hyperlink: None,
+        }
+    }
+
+    /// Sets the dialog's title bar text.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the body text shown below the main instruction. May contain
+    /// `<a href="...">` hyperlinks if
+    /// [`TaskDialog::with_hyperlinks_enabled`] is also set.
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = content.into();
+        self
+    }
+
+    /// Sets the icon shown alongside the main instruction.
+    pub fn with_icon(mut self, icon: TaskDialogIcon) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    /// Controls whether the dialog can be cancelled with Esc or the
+    /// title-bar close button even if no "Cancel" button was added.
+    /// Defaults to `true`.
+    pub fn with_allow_cancellation(mut self, allow: bool) -> Self {
+        self.allow_cancellation = allow;
+        self
+    }
+
+    /// Adds a custom button labelled `text`, reported as
+    /// [`TaskDialogResult::button`] when chosen.
+    pub fn with_button(mut self, id: i32, text: impl Into<String>) -> Self {
+        self.buttons.push((id, text.into()));
+        self
+    }
+
+    /// Adds a radio option labelled `text`, reported as
+    /// [`TaskDialogResult::radio_button`] when the dialog is dismissed.
+    pub fn with_radio_button(mut self, id: i32, text: impl Into<String>) -> Self {
+        self.radio_buttons.push((id, text.into()));
+        self
+    }
+
+    /// Selects `id` as the initially-checked radio option.
+    pub fn with_default_radio_button(mut self, id: i32) -> Self {
+        self.default_radio_button = Some(id);
+        self
+    }
+
+    /// Adds a verification checkbox labelled `text`, initially `checked`,
+    /// reported as [`TaskDialogResult::verification_checked`].
+    pub fn with_verification_text(mut self, text: impl Into<String>, checked: bool) -> Self {
+        self.verification_text = Some((text.into(), checked));
+        self
+    }
+
+    /// Allows `<a href="...">` hyperlinks in the main instruction and
+    /// content text, invoking `on_click` with the link's target whenever
+    /// the user follows one.
+    pub fn with_hyperlinks_enabled(mut self, on_click: impl FnMut(&str) + 'static) -> Self {
+        self.hyperlinks_enabled = true;
+        self.on_hyperlink = Some(Box::new(on_click));
+        self
+    }
+
+    /// Shows a determinate progress bar, initially at `percent` (0-100).
+    /// The bar isn't updated further once the dialog is shown; apps that
+    /// need to advance it over time should dismiss and re-show the dialog
+    /// instead.
+    pub fn with_progress(mut self, percent: u16) -> Self {
+        self.progress_percent = Some(percent.min(100));
+        self
+    }
+
+    /// Shows the dialog modally over `owner` and blocks until the user
+    /// dismisses it.
+    pub fn show(self, owner: &Window) -> Result<TaskDialogResult> {
+        let title = encode_wide(&self.title);
+        let main_instruction = encode_wide(&self.main_instruction);
+        let content = encode_wide(&self.content);
+
+        let button_texts: Vec<Vec<u16>> =
+            self.buttons.iter().map(|(_, t)| encode_wide(t)).collect();
+        let buttons: Vec<TASKDIALOG_BUTTON> = self
+            .buttons
+            .iter()
+            .zip(&button_texts)
+            .map(|((id, _), text)| TASKDIALOG_BUTTON {
+                nButtonID: *id,
+                pszButtonText: PCWSTR(text.as_ptr()),
+            })
+            .collect();
+
+        let radio_texts: Vec<Vec<u16>> = self
+            .radio_buttons
+            .iter()
+            .map(|(_, t)| encode_wide(t))
+            .collect();
+        let radio_buttons: Vec<TASKDIALOG_BUTTON> = self
+            .radio_buttons
+            .iter()
+            .zip(&radio_texts)
+            .map(|((id, _), text)| TASKDIALOG_BUTTON {
+                nButtonID: *id,
+                pszButtonText: PCWSTR(text.as_ptr()),
+            })
+            .collect();
+
+        let verification_text = self
+            .verification_text
+            .as_ref()
+            .map(|(text, _)| encode_wide(text));
+
+        let mut flags = TDF_ALLOW_DIALOG_CANCELLATION;
+        if !self.allow_cancellation {
+            flags = Default::default();
+        }
+        if self.hyperlinks_enabled {
+            flags = flags | TDF_ENABLE_HYPERLINKS;
+        }
+        if matches!(self.verification_text, Some((_, true))) {
+            flags = flags | TDF_VERIFICATION_FLAG_CHECKED;
+        }
+        if self.progress_percent.is_some() {
+            flags = flags | TDF_SHOW_PROGRESS_BAR;
+        }
+
+        let mut state = CallbackState {
+            on_hyperlink: self.on_hyperlink,
+            progress_percent: self.progress_percent,
+        };
+
+        let mut config = TASKDIALOGCONFIG {
+            cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+            hwndParent: owner.hwnd(),
+            dwFlags: flags,
+            pszWindowTitle: PCWSTR(title.as_ptr()),
+            pszMainInstruction: PCWSTR(main_instruction.as_ptr()),
+            pszContent: PCWSTR(content.as_ptr()),
+            cButtons: buttons.len() as u32,
+            pButtons: buttons.as_ptr(),
+            cRadioButtons: radio_buttons.len() as u32,
+            pRadioButtons: radio_buttons.as_ptr(),
+            nDefaultRadioButton: self.default_radio_button.unwrap_or(0),
+            pszVerificationText: verification_text
+                .as_ref()
+                .map_or(PCWSTR::null(), |text| PCWSTR(text.as_ptr())),
+            pfCallback: Some(task_dialog_proc),
+            lpCallbackData: &mut state as *mut CallbackState as isize,
+            ..Default::default()
+        };
+        config.Anonymous1.pszMainIcon = self.icon.pcwstr();
+
+        let mut button_id = 0;
+        let mut radio_button_id = 0;
+        let mut verification_checked = windows::core::BOOL(0);
+
+        // SAFETY: every `PCWSTR` field above points into a buffer that
+        // outlives this call, and `config`/`buttons`/`radio_buttons` are
+        // all still alive; `lpCallbackData` points at `state`, which is
+        // also still alive for the duration of the call.
+        unsafe {
+            TaskDialogIndirect(
+                &config,
+                Some(&mut button_id),
+                Some(&mut radio_button_id),
+                Some(&mut verification_checked),
+            )
+        }
+        .map_err(Error::from)?;
+
+        Ok(TaskDialogResult {
+            button: button_id,
+            radio_button: if radio_buttons.is_empty() {
+                None
+            } else {
+                Some(radio_button_id)
+            },
+            verification_checked: verification_checked.as_bool(),
+        })
+    }
+}
+
+/// State reachable from [`task_dialog_proc`] via `TASKDIALOGCONFIG`'s
+/// `lpCallbackData`.
+struct CallbackState {
+    on_hyperlink: Option<Box<dyn FnMut(&str)>>,
+    progress_percent: Option<u16>,
+}
+
+/// The callback skylight registers via `TASKDIALOGCONFIG::pfCallback`.
+unsafe extern "system" fn task_dialog_proc(
+    hwnd: HWND,
+    msg: TASKDIALOG_NOTIFICATIONS,
+    _wparam: WPARAM,
+    lparam: LPARAM,
+    lprefdata: isize,
+) -> HRESULT {
+    let state = &mut *(lprefdata as *mut CallbackState);
+
+    if msg == TDN_CREATED {
+        if let Some(percent) = state.progress_percent {
+            SendMessageW(
+                hwnd,
+                TDM_SET_PROGRESS_BAR_POS.0 as u32,
+                Some(WPARAM(percent as usize)),
+                Some(LPARAM(0)),
+            );
+        }
+    } else if msg == TDN_HYPERLINK_CLICKED {
+        if let Some(on_hyperlink) = state.on_hyperlink.as_mut() {
+            // SAFETY: for `TDN_HYPERLINK_CLICKED`, `lparam` points to a
+            // null-terminated string naming the link's `href` target,
+            // valid for the duration of this notification.
+            if let Ok(url) = PCWSTR(lparam.0 as *const u16).to_string() {
+                on_hyperlink(&url);
+            }
+        }
+    }
+
+    HRESULT(0)
+}
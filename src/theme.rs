@@ -0,0 +1,204 @@
+//! Light/dark window theming via the Desktop Window Manager.
+
+use windows::core::w;
+use windows::Win32::Foundation::{BOOL, ERROR_SUCCESS, HWND};
+use windows::Win32::Graphics::Dwm::{
+    DwmSetWindowAttribute, DWMSBT_AUTO, DWMSBT_MAINWINDOW, DWMSBT_NONE, DWMSBT_TABBEDWINDOW,
+    DWMSBT_TRANSIENTWINDOW, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_USE_IMMERSIVE_DARK_MODE,
+    DWMWA_WINDOW_CORNER_PREFERENCE, DWMWCP_DEFAULT, DWMWCP_DONOTROUND, DWMWCP_ROUND,
+    DWMWCP_ROUNDSMALL, DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
+};
+use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+
+/// The visual theme applied to a window's non-client area (title bar, etc).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    /// Light title bar and window chrome.
+    #[default]
+    Light,
+    /// Dark title bar and window chrome.
+    Dark,
+    /// Tracks the OS-wide "apps use light/dark theme" setting, re-applying
+    /// itself when the setting changes while the window is open.
+    FollowSystem,
+}
+
+impl Theme {
+    /// Resolves [`Theme::FollowSystem`] to [`Theme::Light`] or
+    /// [`Theme::Dark`] based on the current OS setting; other variants are
+    /// returned unchanged.
+    fn resolve(self) -> Self {
+        match self {
+            Theme::FollowSystem => {
+                if system_prefers_light() {
+                    Theme::Light
+                } else {
+                    Theme::Dark
+                }
+            }
+            theme => theme,
+        }
+    }
+
+    /// Applies this theme to a window's non-client area.
+    ///
+    /// This is best-effort: on builds of Windows that predate immersive dark
+    /// mode the call simply fails and is ignored, so callers don't need to
+    /// special-case older systems.
+    pub(crate) fn apply(self, hwnd: HWND) {
+        let enabled = BOOL::from(self.resolve() == Theme::Dark);
+        // SAFETY: `hwnd` is a valid window handle and `enabled` matches the
+        // `BOOL`-sized attribute that DWMWA_USE_IMMERSIVE_DARK_MODE expects.
+        let _ = unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_USE_IMMERSIVE_DARK_MODE,
+                std::ptr::addr_of!(enabled).cast(),
+                std::mem::size_of::<BOOL>() as u32,
+            )
+        };
+    }
+}
+
+/// An RGB color, e.g. for [`crate::window::Window::set_caption_color`] and
+/// related window chrome customization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    /// Creates a new color from its components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Packs this color into a Win32 `COLORREF` (`0x00BBGGRR`).
+    pub(crate) fn to_colorref(self) -> u32 {
+        (u32::from(self.b) << 16) | (u32::from(self.g) << 8) | u32::from(self.r)
+    }
+}
+
+/// The system backdrop material applied to a window's client area, for the
+/// modern translucent look introduced in Windows 11 (Windows 10 and older
+/// simply ignore it).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backdrop {
+    /// Let Windows choose a backdrop automatically.
+    #[default]
+    Auto,
+    /// No special backdrop.
+    None,
+    /// The Mica material used by top-level app windows.
+    Mica,
+    /// The Mica Alt material used by tabbed windows.
+    Tabbed,
+    /// The acrylic-like material used by transient surfaces (e.g. menus).
+    Acrylic,
+}
+
+impl Backdrop {
+    /// Applies this backdrop to a window's client area.
+    ///
+    /// This is best-effort: on builds of Windows that predate system
+    /// backdrops the call simply fails and is ignored, so callers don't
+    /// need to special-case older systems.
+    pub(crate) fn apply(self, hwnd: HWND) {
+        let backdrop_type = DWM_SYSTEMBACKDROP_TYPE::from(self);
+        // SAFETY: `hwnd` is a valid window handle and `backdrop_type`
+        // matches the size DWMWA_SYSTEMBACKDROP_TYPE expects.
+        let _ = unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                std::ptr::addr_of!(backdrop_type).cast(),
+                std::mem::size_of::<DWM_SYSTEMBACKDROP_TYPE>() as u32,
+            )
+        };
+    }
+}
+
+impl From<Backdrop> for DWM_SYSTEMBACKDROP_TYPE {
+    fn from(backdrop: Backdrop) -> Self {
+        match backdrop {
+            Backdrop::Auto => DWMSBT_AUTO,
+            Backdrop::None => DWMSBT_NONE,
+            Backdrop::Mica => DWMSBT_MAINWINDOW,
+            Backdrop::Tabbed => DWMSBT_TABBEDWINDOW,
+            Backdrop::Acrylic => DWMSBT_TRANSIENTWINDOW,
+        }
+    }
+}
+
+/// A window's corner rounding preference (Windows 11 only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CornerPreference {
+    /// Let Windows decide whether to round the window's corners.
+    #[default]
+    Default,
+    /// Never round the window's corners, e.g. for embedded surfaces that
+    /// need square edges.
+    DoNotRound,
+    /// Always round the window's corners.
+    Round,
+    /// Always round the window's corners, using a smaller radius.
+    RoundSmall,
+}
+
+impl CornerPreference {
+    /// Applies this corner preference to a window.
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored, so callers don't
+    /// need to special-case older systems.
+    pub(crate) fn apply(self, hwnd: HWND) {
+        let preference = DWM_WINDOW_CORNER_PREFERENCE::from(self);
+        // SAFETY: `hwnd` is a valid window handle and `preference` matches
+        // the size DWMWA_WINDOW_CORNER_PREFERENCE expects.
+        let _ = unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                DWMWA_WINDOW_CORNER_PREFERENCE,
+                std::ptr::addr_of!(preference).cast(),
+                std::mem::size_of::<DWM_WINDOW_CORNER_PREFERENCE>() as u32,
+            )
+        };
+    }
+}
+
+impl From<CornerPreference> for DWM_WINDOW_CORNER_PREFERENCE {
+    fn from(preference: CornerPreference) -> Self {
+        match preference {
+            CornerPreference::Default => DWMWCP_DEFAULT,
+            CornerPreference::DoNotRound => DWMWCP_DONOTROUND,
+            CornerPreference::Round => DWMWCP_ROUND,
+            CornerPreference::RoundSmall => DWMWCP_ROUNDSMALL,
+        }
+    }
+}
+
+/// Reads the OS-wide `AppsUseLightTheme` registry value, defaulting to
+/// `true` if it can't be read (e.g. on builds of Windows that predate this
+/// setting).
+fn system_prefers_light() -> bool {
+    let mut value: u32 = 1;
+    let mut size = std::mem::size_of::<u32>() as u32;
+
+    // SAFETY: `value` and `size` describe a single `u32` out-buffer of the
+    // size just declared.
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            w!(r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize"),
+            w!("AppsUseLightTheme"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some(std::ptr::addr_of_mut!(value).cast()),
+            Some(&mut size),
+        )
+    };
+
+    status != ERROR_SUCCESS || value != 0
+}
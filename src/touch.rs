@@ -0,0 +1,79 @@
+//! Multi-touch contact tracking fed by `WM_POINTER*` messages.
+
+use windows::Win32::Foundation::{HWND, WPARAM};
+use windows::Win32::Graphics::Gdi::ScreenToClient;
+use windows::Win32::UI::Input::Pointer::{GetPointerTouchInfo, POINTER_TOUCH_INFO};
+use windows::Win32::UI::WindowsAndMessaging::{PT_TOUCH, WM_POINTERDOWN, WM_POINTERUP};
+
+use crate::geometry::Point2D;
+
+/// The stage of a [`TouchContact`]'s lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// The finger touched down.
+    Down,
+    /// The finger moved while still in contact.
+    Moved,
+    /// The finger lifted off.
+    Up,
+}
+
+/// A single finger's state, as of one `WM_POINTER*` message.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchContact {
+    /// Identifies this finger for the duration of its contact, so
+    /// `Down`/`Moved`/`Up` events for the same finger can be correlated.
+    pub id: u32,
+    /// The contact's position in client coordinates.
+    pub position: Point2D<i32>,
+    /// Normalized pressure, from `0.0` to `1.0`. Not every digitizer
+    /// reports pressure; those report a constant `1.0`.
+    pub pressure: f32,
+    /// The stage of the contact's lifetime this message represents.
+    pub phase: TouchPhase,
+}
+
+impl TouchContact {
+    /// Extracts a touch contact from a `WM_POINTERDOWN`/`WM_POINTERUPDATE`/
+    /// `WM_POINTERUP` message, or returns `None` if the pointer that
+    /// generated it isn't a touch contact (e.g. it's a mouse or pen, or
+    /// the pointer has already been discarded).
+    pub(crate) fn from_message(hwnd: HWND, msg: u32, wparam: WPARAM) -> Option<Self> {
+        // The pointer ID is the low word of `wParam`, per the
+        // `GET_POINTERID_WPARAM` macro.
+        let pointer_id = (wparam.0 & 0xffff) as u32;
+
+        let mut info = POINTER_TOUCH_INFO::default();
+        // SAFETY: `info` is a valid out-parameter for the duration of the
+        // call.
+        unsafe { GetPointerTouchInfo(pointer_id, &mut info) }.ok()?;
+
+        if info.pointerInfo.pointerType != PT_TOUCH {
+            return None;
+        }
+
+        let mut point = info.pointerInfo.ptPixelLocation;
+        // SAFETY: `point` is a valid in/out parameter and `hwnd` is the
+        // window that received the originating message.
+        unsafe { ScreenToClient(hwnd, &mut point) };
+
+        let phase = match msg {
+            WM_POINTERDOWN => TouchPhase::Down,
+            WM_POINTERUP => TouchPhase::Up,
+            _ => TouchPhase::Moved,
+        };
+
+        Some(Self {
+            id: info.pointerInfo.pointerId,
+            position: Point2D::new(point.x, point.y),
+            // `0x4` is `TOUCH_MASK_PRESSURE`: without it, `pressure` holds
+            // no reading.
+            pressure: if info.touchMask & 0x4 != 0 {
+                (info.pressure as f32 / 1024.0).min(1.0)
+            } else {
+                1.0
+            },
+            phase,
+        })
+    }
+}
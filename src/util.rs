@@ -0,0 +1,6 @@
+//! Small helpers shared across modules that talk to Win32 string APIs.
+
+/// Encodes `text` as a null-terminated UTF-16 string.
+pub(crate) fn encode_wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
@@ -0,0 +1,49 @@
+//! The undocumented `SetWindowCompositionAttribute` accent-blur policies,
+//! reverse-engineered from `user32.dll` rather than published by
+//! Microsoft. Only linked in when the `accent_policy` feature opts into
+//! depending on them.
+
+use windows::Win32::Foundation::HWND;
+
+/// An accent-blur policy applied via
+/// [`crate::window::Window::set_accent_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccentPolicy {
+    /// Disables the accent effect, restoring the ordinary window background.
+    Disabled,
+    /// A gaussian blur of whatever is behind the window.
+    BlurBehind,
+    /// A tinted, noisy acrylic-style blur. `tint` blends into the blur as
+    /// `0xAABBGGRR`.
+    AcrylicBlurBehind { tint: u32 },
+}
+
+pub(super) const ACCENT_DISABLED: u32 = 0;
+pub(super) const ACCENT_ENABLE_BLURBEHIND: u32 = 3;
+pub(super) const ACCENT_ENABLE_ACRYLICBLURBEHIND: u32 = 4;
+pub(super) const WCA_ACCENT_POLICY: u32 = 19;
+
+#[repr(C)]
+pub(super) struct AccentPolicyRaw {
+    pub accent_state: u32,
+    pub accent_flags: u32,
+    pub gradient_color: u32,
+    pub animation_id: u32,
+}
+
+#[repr(C)]
+pub(super) struct WindowCompositionAttribData {
+    pub attribute: u32,
+    pub data: *const AccentPolicyRaw,
+    pub size_of_data: u32,
+}
+
+/// Calls the undocumented `user32.dll` export of the same name, setting
+/// `*data`'s attribute on `hwnd`.
+pub(super) unsafe fn set_window_composition_attribute(
+    hwnd: HWND,
+    data: *const WindowCompositionAttribData,
+) -> windows::core::Result<()> {
+    windows::core::link!("user32.dll" "system" fn SetWindowCompositionAttribute(hwnd: HWND, data: *const WindowCompositionAttribData) -> windows::core::BOOL);
+    unsafe { SetWindowCompositionAttribute(hwnd, data).ok() }
+}
@@ -0,0 +1,44 @@
+//! Registration of the shared Win32 window class used by every [`super::Window`].
+
+use std::sync::OnceLock;
+
+use windows::core::{w, PCWSTR};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    LoadCursorW, RegisterClassExW, CS_HREDRAW, CS_VREDRAW, IDC_ARROW, WNDCLASSEXW,
+};
+
+use super::inner::wnd_proc;
+
+/// The class name shared by every skylight-managed window.
+pub(super) const CLASS_NAME: PCWSTR = w!("Skylight::Window");
+
+/// Registers the shared window class the first time a window is built.
+pub(super) fn register() {
+    static REGISTERED: OnceLock<()> = OnceLock::new();
+    REGISTERED.get_or_init(|| {
+        // SAFETY: `GetModuleHandleW(None)` returns a handle to the current
+        // module, which is always valid for the lifetime of the process.
+        let instance = unsafe { GetModuleHandleW(None) }.unwrap_or_default();
+        // SAFETY: `IDC_ARROW` is a built-in cursor resource that always
+        // exists.
+        let cursor = unsafe { LoadCursorW(None, IDC_ARROW) }.unwrap_or_default();
+
+        let class = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            style: CS_HREDRAW | CS_VREDRAW,
+            lpfnWndProc: Some(wnd_proc),
+            hInstance: instance.into(),
+            hCursor: cursor,
+            lpszClassName: CLASS_NAME,
+            ..Default::default()
+        };
+
+        // SAFETY: `class` is fully initialized; `lpfnWndProc` points to a
+        // `'static` function, so the registration is valid for the
+        // lifetime of the process.
+        unsafe {
+            RegisterClassExW(&class);
+        }
+    });
+}
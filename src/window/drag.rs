@@ -0,0 +1,224 @@
+//! COM glue backing [`super::Window::start_drag`]: a minimal `IDataObject`
+//! exposing text or file paths, and an `IDropSource` that simply tracks the
+//! mouse buttons and Escape key.
+
+use std::mem::size_of;
+use std::os::windows::ffi::OsStrExt;
+use std::path::PathBuf;
+
+use windows::core::{implement, Error as WinError, Ref, Result as WinResult, BOOL, HRESULT};
+use windows::Win32::Foundation::{DV_E_FORMATETC, E_NOTIMPL, HGLOBAL, POINT};
+use windows::Win32::System::Com::{
+    IAdviseSink, IDataObject, IDataObject_Impl, IEnumFORMATETC, IEnumSTATDATA, FORMATETC,
+    STGMEDIUM, STGMEDIUM_0, TYMED_HGLOBAL,
+};
+use windows::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+use windows::Win32::System::Ole::{
+    IDropSource, IDropSource_Impl, CF_HDROP, CF_UNICODETEXT, DRAGDROP_S_CANCEL, DRAGDROP_S_DROP,
+    DRAGDROP_S_USEDEFAULTCURSORS, DROPEFFECT, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE,
+    OLE_E_ADVISENOTSUPPORTED,
+};
+use windows::Win32::System::SystemServices::{MK_LBUTTON, MK_RBUTTON, MODIFIERKEYS_FLAGS};
+use windows::Win32::UI::Shell::DROPFILES;
+
+use super::{DragData, DropEffect};
+
+/// A data object offering a single format, built from a [`DragData`] value.
+#[implement(IDataObject)]
+pub(super) struct DataObject {
+    data: DragData,
+}
+
+impl DataObject {
+    pub(super) fn new(data: DragData) -> Self {
+        Self { data }
+    }
+
+    /// The clipboard format this data object can satisfy.
+    fn format(&self) -> u16 {
+        match &self.data {
+            DragData::Text(_) => CF_UNICODETEXT.0,
+            DragData::Paths(_) => CF_HDROP.0,
+        }
+    }
+
+    fn build_medium(&self) -> WinResult<STGMEDIUM> {
+        let hglobal = match &self.data {
+            DragData::Text(text) => utf16_hglobal(text)?,
+            DragData::Paths(paths) => hdrop_hglobal(paths)?,
+        };
+        Ok(STGMEDIUM {
+            tymed: TYMED_HGLOBAL.0 as u32,
+            u: STGMEDIUM_0 { hGlobal: hglobal },
+            pUnkForRelease: Default::default(),
+        })
+    }
+}
+
+impl IDataObject_Impl for DataObject_Impl {
+    fn GetData(&self, format: *const FORMATETC) -> WinResult<STGMEDIUM> {
+        // SAFETY: `format` is a valid in-parameter for the duration of the
+        // call, as guaranteed by `IDataObject::GetData`'s contract.
+        let format = unsafe { &*format };
+        if format.cfFormat != self.format() || format.tymed & TYMED_HGLOBAL.0 as u32 == 0 {
+            return Err(WinError::from(DV_E_FORMATETC));
+        }
+        self.build_medium()
+    }
+
+    fn GetDataHere(&self, _format: *const FORMATETC, _medium: *mut STGMEDIUM) -> WinResult<()> {
+        Err(WinError::from(E_NOTIMPL))
+    }
+
+    fn QueryGetData(&self, format: *const FORMATETC) -> HRESULT {
+        // SAFETY: `format` is a valid in-parameter for the duration of the
+        // call, as guaranteed by `IDataObject::QueryGetData`'s contract.
+        let format = unsafe { &*format };
+        if format.cfFormat == self.format() && format.tymed & TYMED_HGLOBAL.0 as u32 != 0 {
+            HRESULT(0)
+        } else {
+            DV_E_FORMATETC
+        }
+    }
+
+    fn GetCanonicalFormatEtc(
+        &self,
+        _format_in: *const FORMATETC,
+        _format_out: *mut FORMATETC,
+    ) -> HRESULT {
+        E_NOTIMPL
+    }
+
+    fn SetData(
+        &self,
+        _format: *const FORMATETC,
+        _medium: *const STGMEDIUM,
+        _release: BOOL,
+    ) -> WinResult<()> {
+        Err(WinError::from(E_NOTIMPL))
+    }
+
+    fn EnumFormatEtc(&self, _direction: u32) -> WinResult<IEnumFORMATETC> {
+        Err(WinError::from(E_NOTIMPL))
+    }
+
+    fn DAdvise(
+        &self,
+        _format: *const FORMATETC,
+        _advf: u32,
+        _sink: Ref<IAdviseSink>,
+    ) -> WinResult<u32> {
+        Err(WinError::from(OLE_E_ADVISENOTSUPPORTED))
+    }
+
+    fn DUnadvise(&self, _connection: u32) -> WinResult<()> {
+        Err(WinError::from(E_NOTIMPL))
+    }
+
+    fn EnumDAdvise(&self) -> WinResult<IEnumSTATDATA> {
+        Err(WinError::from(OLE_E_ADVISENOTSUPPORTED))
+    }
+}
+
+/// A drop source that cancels the drag when Escape is pressed and completes
+/// it once both the left and right mouse buttons have been released, using
+/// the system's default drag cursors throughout.
+#[implement(IDropSource)]
+pub(super) struct DropSource;
+
+impl IDropSource_Impl for DropSource_Impl {
+    fn QueryContinueDrag(&self, escape_pressed: BOOL, key_state: MODIFIERKEYS_FLAGS) -> HRESULT {
+        if escape_pressed.as_bool() {
+            DRAGDROP_S_CANCEL
+        } else if !key_state.contains(MK_LBUTTON) && !key_state.contains(MK_RBUTTON) {
+            DRAGDROP_S_DROP
+        } else {
+            HRESULT(0)
+        }
+    }
+
+    fn GiveFeedback(&self, _effect: DROPEFFECT) -> HRESULT {
+        DRAGDROP_S_USEDEFAULTCURSORS
+    }
+}
+
+/// Allocates a null-terminated UTF-16 global memory block holding `text`,
+/// suitable for a `CF_UNICODETEXT` / `TYMED_HGLOBAL` transfer.
+fn utf16_hglobal(text: &str) -> WinResult<HGLOBAL> {
+    let mut utf16: Vec<u16> = text.encode_utf16().collect();
+    utf16.push(0);
+    let byte_len = utf16.len() * size_of::<u16>();
+
+    // SAFETY: `byte_len` is a valid, non-zero allocation size.
+    let hmem = unsafe { GlobalAlloc(GMEM_MOVEABLE, byte_len) }?;
+
+    // SAFETY: `hmem` was just allocated above with room for `byte_len`
+    // bytes.
+    let ptr = unsafe { GlobalLock(hmem) };
+    if ptr.is_null() {
+        return Err(WinError::from_win32());
+    }
+    // SAFETY: `ptr` is valid for `byte_len` bytes while locked, matching
+    // `utf16`'s length.
+    unsafe {
+        std::ptr::copy_nonoverlapping(utf16.as_ptr(), ptr.cast::<u16>(), utf16.len());
+        let _ = GlobalUnlock(hmem);
+    }
+
+    Ok(hmem)
+}
+
+/// Allocates a `DROPFILES` global memory block listing `paths`, suitable
+/// for a `CF_HDROP` / `TYMED_HGLOBAL` transfer.
+fn hdrop_hglobal(paths: &[PathBuf]) -> WinResult<HGLOBAL> {
+    let mut names: Vec<u16> = Vec::new();
+    for path in paths {
+        names.extend(path.as_os_str().encode_wide());
+        names.push(0);
+    }
+    names.push(0);
+
+    let header_size = size_of::<DROPFILES>();
+    let byte_len = header_size + names.len() * size_of::<u16>();
+
+    // SAFETY: `byte_len` is a valid, non-zero allocation size.
+    let hmem = unsafe { GlobalAlloc(GMEM_MOVEABLE, byte_len) }?;
+
+    // SAFETY: `hmem` was just allocated above with room for `byte_len`
+    // bytes.
+    let ptr = unsafe { GlobalLock(hmem) };
+    if ptr.is_null() {
+        return Err(WinError::from_win32());
+    }
+    // SAFETY: `ptr` is valid for `byte_len` bytes while locked: a
+    // `DROPFILES` header followed by `names`' double-null-terminated,
+    // null-separated UTF-16 paths.
+    unsafe {
+        ptr.cast::<DROPFILES>().write(DROPFILES {
+            pFiles: header_size as u32,
+            pt: POINT::default(),
+            fNC: false.into(),
+            fWide: true.into(),
+        });
+        let names_ptr = ptr.byte_add(header_size).cast::<u16>();
+        std::ptr::copy_nonoverlapping(names.as_ptr(), names_ptr, names.len());
+        let _ = GlobalUnlock(hmem);
+    }
+
+    Ok(hmem)
+}
+
+/// Converts the `DROPEFFECT` flags `DoDragDrop` reports back to a
+/// [`DropEffect`], preferring copy, then move, then link if the target
+/// reported more than one.
+pub(super) fn from_dropeffect(effect: DROPEFFECT) -> DropEffect {
+    if effect.contains(DROPEFFECT_COPY) {
+        DropEffect::Copy
+    } else if effect.contains(DROPEFFECT_MOVE) {
+        DropEffect::Move
+    } else if effect.contains(DROPEFFECT_LINK) {
+        DropEffect::Link
+    } else {
+        DropEffect::None
+    }
+}
@@ -0,0 +1,1330 @@
+//! Per-window state and the shared `WNDPROC` trampoline.
+
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+
+use windows::core::PCWSTR;
+#[cfg(feature = "device_notifications")]
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    ClientToScreen, CreateSolidBrush, DeleteObject, FillRect, GetUpdateRect, InvalidateRect,
+    ScreenToClient, HDC,
+};
+use windows::Win32::UI::Input::Ime::{ImmAssociateContext, HIMC};
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+    ReleaseCapture, SetCapture, MOUSE_MOVE_ABSOLUTE, RAWINPUTDEVICE,
+};
+use windows::Win32::UI::Input::{
+    GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT, RAWINPUTHEADER, RIDEV_INPUTSINK,
+    RID_INPUT, RIM_TYPEMOUSE,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    ClipCursor, DefWindowProcW, GetClientRect, KillTimer, LoadCursorW, PeekMessageW, SendMessageW,
+    SetCursor, SetTimer, SetWindowPos, CREATESTRUCTW, GWLP_USERDATA, HCURSOR, HTCAPTION, HTCLIENT,
+    HTCLOSE, HTMAXBUTTON, HTMINBUTTON, ICON_BIG, ICON_SMALL, IDC_ARROW, MINMAXINFO, MSG, PM_REMOVE,
+    SIZE_MAXIMIZED, SIZE_MINIMIZED, SIZE_RESTORED, SWP_NOACTIVATE, SWP_NOZORDER, UNICODE_NOCHAR,
+    WM_APPCOMMAND, WM_CAPTURECHANGED, WM_CHAR, WM_CLOSE, WM_COMMAND, WM_DESTROY, WM_DPICHANGED,
+    WM_ENDSESSION, WM_ENTERSIZEMOVE, WM_ERASEBKGND, WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_INPUT,
+    WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
+    WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCCALCSIZE, WM_NCCREATE, WM_NCHITTEST,
+    WM_PAINT, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_POWERBROADCAST, WM_PRINTCLIENT,
+    WM_QUERYENDSESSION, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETICON,
+    WM_SETTINGCHANGE, WM_SIZE, WM_TIMER, WM_UNICHAR, WM_USER, WM_XBUTTONDOWN, WM_XBUTTONUP,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetWindowLongPtrW, PostQuitMessage, SetMenu, SetWindowLongPtrW,
+};
+#[cfg(feature = "device_notifications")]
+use windows::Win32::UI::WindowsAndMessaging::{
+    RegisterDeviceNotificationW, UnregisterDeviceNotification, DBT_DEVICEARRIVAL,
+    DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_DEVICEINTERFACE, DEVICE_NOTIFY_WINDOW_HANDLE,
+    DEV_BROADCAST_DEVICEINTERFACE_W, DEV_BROADCAST_HDR, HDEVNOTIFY, WM_DEVICECHANGE,
+};
+
+#[cfg(feature = "device_notifications")]
+use crate::device::{DeviceClass, DeviceEvent};
+use crate::dpi::Dpi;
+use crate::error::{Error, Result};
+use crate::event_loop::Event;
+use crate::geometry::{Point2D, Rect2D, Size2D};
+use crate::gesture::{Gesture, GestureRecognizer};
+use crate::handler::{CloseResponse, WindowHandler};
+use crate::keyboard::{KeyCode, KeyEvent, Keyboard};
+use crate::media::MediaCommand;
+use crate::menu::Menu;
+use crate::mouse::{Mouse, MouseClick, WheelDelta};
+use crate::power::PowerEvent;
+use crate::shell::Icon;
+use crate::theme::{Color, Theme};
+use crate::touch::TouchContact;
+
+use super::{CaptionButton, CursorGrab, CustomCursor, WindowHandle, WindowState};
+
+/// The custom message used to deliver events posted via
+/// [`super::UserEventSender::post`].
+pub(super) const WM_USER_EVENT: u32 = WM_USER + 1;
+
+/// The `SetTimer` ID used to drive a paint tick during the modal
+/// resize/move loop, when [`super::Builder::with_live_resize`] is enabled.
+const LIVE_RESIZE_TIMER_ID: usize = 1;
+
+/// The approximate interval, in milliseconds, of the paint tick driven
+/// during the modal resize/move loop.
+const LIVE_RESIZE_TICK_MS: u32 = 16;
+
+/// The number of windows created via [`super::Builder::build`] or taken
+/// over via [`super::Window::adopt`] that haven't yet received their own
+/// `WM_DESTROY`. `WM_DESTROY` only posts
+/// `WM_QUIT` once this reaches zero, so closing one window in a
+/// multi-window [`crate::app::WindowSet`] app doesn't tear down the whole
+/// message loop.
+static LIVE_WINDOWS: AtomicUsize = AtomicUsize::new(0);
+
+/// Registers a newly created window, so its eventual `WM_DESTROY` doesn't
+/// post `WM_QUIT` while other windows are still alive.
+pub(super) fn register_window() {
+    LIVE_WINDOWS.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Per-window state addressed via the `GWLP_USERDATA` slot, so the
+/// free-standing [`wnd_proc`] can recover it for any `HWND`.
+pub(super) struct WindowInner {
+    pub(super) keyboard: RefCell<Keyboard>,
+    pub(super) mouse: RefCell<Mouse>,
+    pub(super) dpi: Cell<Dpi>,
+    pub(super) theme: Cell<Theme>,
+    pub(super) size: Cell<Size2D<i32>>,
+    pub(super) requesting_close: Cell<bool>,
+    /// Set on `WM_QUERYENDSESSION` if the handler allowed the shutdown, and
+    /// cleared again if `WM_ENDSESSION` reports the shutdown was canceled.
+    pub(super) requesting_shutdown: Cell<bool>,
+    pub(super) requesting_paint: Cell<bool>,
+    /// Bumped on every `WM_PAINT`, so callers can detect repaints even
+    /// though `requesting_paint` never resets on its own.
+    pub(super) paint_generation: Cell<u64>,
+    /// The invalid region from the most recent `WM_PAINT`, not yet
+    /// consumed via [`WindowInner::take_paint_request`].
+    pending_paint_rect: Cell<Option<Rect2D<i32>>>,
+    /// The most recent size reported by `WM_SIZE`, if any, not yet
+    /// consumed via [`WindowInner::take_resize`].
+    pending_resize: Cell<Option<Size2D<i32>>>,
+    /// The HDC supplied by the `WM_PRINTCLIENT` currently being handled, if
+    /// any, for [`super::Window::print_client_target`]. Unlike `WM_PAINT`,
+    /// `WM_PRINTCLIENT` hands the HDC to paint into directly via `wparam`
+    /// rather than expecting `BeginPaint` to retrieve one.
+    print_client_target: Cell<Option<HDC>>,
+    /// Key transitions not yet consumed via
+    /// [`WindowInner::drain_key_transitions`].
+    key_transitions: RefCell<VecDeque<(KeyCode, bool)>>,
+    raw_mouse_deltas: RefCell<VecDeque<(i32, i32)>>,
+    /// The application-supplied handler, if any, set via
+    /// [`super::Builder::with_handler`].
+    handler: RefCell<Option<Box<dyn WindowHandler>>>,
+    /// The smallest outer window size, frame included, the user may resize
+    /// the window to, if constrained.
+    min_size: Cell<Option<Size2D<i32>>>,
+    /// The largest outer window size, frame included, the user may resize
+    /// the window to, if constrained.
+    max_size: Cell<Option<Size2D<i32>>>,
+    /// The most recent minimize/maximize/restore transition reported by
+    /// `WM_SIZE`, not yet consumed via
+    /// [`WindowInner::take_state_change`].
+    pending_state_change: Cell<Option<WindowState>>,
+    /// Set once the window has received at least one `WM_DPICHANGED`.
+    requesting_dpi_change: Cell<bool>,
+    /// Bumped on every `WM_DPICHANGED`, so callers can detect DPI changes
+    /// even though `requesting_dpi_change` never resets on its own.
+    dpi_generation: Cell<u64>,
+    /// Set once the system theme or high-contrast setting has changed,
+    /// independent of whether [`Theme::FollowSystem`] is in use.
+    requesting_theme_change: Cell<bool>,
+    /// Bumped on every system theme/high-contrast change, so callers can
+    /// detect changes even though `requesting_theme_change` never resets
+    /// on its own.
+    theme_change_generation: Cell<u64>,
+    /// `false` for a caption-less window that draws its own title bar, set
+    /// via [`super::Builder::with_decorations`].
+    decorations: Cell<bool>,
+    /// Client-area regions that behave like the title bar, registered via
+    /// [`super::Window::set_drag_regions`].
+    drag_regions: RefCell<Vec<Rect2D<i32>>>,
+    /// Client-area regions that behave like caption buttons, registered via
+    /// [`super::Window::set_caption_buttons`].
+    caption_buttons: RefCell<Vec<(CaptionButton, Rect2D<i32>)>>,
+    /// Whether the window currently has keyboard focus, tracked via
+    /// `WM_SETFOCUS`/`WM_KILLFOCUS`.
+    focused: Cell<bool>,
+    /// The cursor shown over the client area, set via
+    /// [`super::Window::set_cursor`].
+    cursor: Cell<HCURSOR>,
+    /// The currently active cursor, if it is a [`CustomCursor`] set via
+    /// [`super::Window::set_custom_cursor`], kept alive for as long as it
+    /// remains active.
+    active_custom_cursor: RefCell<Option<CustomCursor>>,
+    /// The icon set via [`super::Window::set_icon`] or
+    /// [`super::Builder::with_icon`], kept alive for as long as it remains
+    /// the window's icon, since `WM_SETICON` does not take ownership.
+    active_icon: RefCell<Option<Icon>>,
+    /// The cursor confinement mode, set via
+    /// [`super::Window::set_cursor_grab`]. Re-applied on `WM_SETFOCUS`,
+    /// since Windows automatically releases the clip when the window loses
+    /// focus.
+    cursor_grab: Cell<CursorGrab>,
+    /// The menu bar attached via [`super::Window::set_menu_bar`], kept
+    /// alive for as long as it remains attached.
+    menu_bar: RefCell<Option<Menu>>,
+    /// Menu selections reported by `WM_COMMAND`, not yet consumed via
+    /// [`WindowInner::drain_menu_selections`].
+    menu_selections: RefCell<VecDeque<u32>>,
+    /// Child control notifications (id, notification code) reported by
+    /// `WM_COMMAND`, not yet consumed via
+    /// [`WindowInner::drain_control_notifications`].
+    control_notifications: RefCell<VecDeque<(u32, u32)>>,
+    /// The window's own input context, saved by
+    /// [`WindowInner::set_text_input_enabled`] while the IME is disabled, so
+    /// it can be restored on re-enable.
+    saved_ime_context: Cell<Option<HIMC>>,
+    /// Media/browser commands reported by `WM_APPCOMMAND`, not yet consumed
+    /// via [`WindowInner::drain_media_commands`].
+    media_commands: RefCell<VecDeque<MediaCommand>>,
+    /// Power/battery notifications reported by `WM_POWERBROADCAST`, not
+    /// yet consumed via [`WindowInner::drain_power_events`].
+    power_events: RefCell<VecDeque<PowerEvent>>,
+    /// Handles returned by `RegisterDeviceNotificationW` for each
+    /// [`DeviceClass`] registered via
+    /// [`WindowInner::register_device_notifications`], unregistered on
+    /// `WM_DESTROY`.
+    #[cfg(feature = "device_notifications")]
+    device_notify_handles: RefCell<Vec<HDEVNOTIFY>>,
+    /// Device arrival/removal notifications reported by `WM_DEVICECHANGE`,
+    /// not yet consumed via [`WindowInner::drain_device_events`].
+    #[cfg(feature = "device_notifications")]
+    device_events: RefCell<VecDeque<(DeviceClass, DeviceEvent)>>,
+    /// Touch contacts reported by `WM_POINTER*`, not yet consumed via
+    /// [`WindowInner::drain_touch_contacts`].
+    touch_contacts: RefCell<VecDeque<TouchContact>>,
+    /// Derives pinch/rotate/pan [`Gesture`]s from `touch_contacts` as they
+    /// arrive.
+    gesture_recognizer: RefCell<GestureRecognizer>,
+    /// Gestures recognized from touch contacts, not yet consumed via
+    /// [`WindowInner::drain_gestures`].
+    gestures: RefCell<VecDeque<Gesture>>,
+    /// Mouse-wheel deltas reported by `WM_MOUSEWHEEL`/`WM_MOUSEHWHEEL`, not
+    /// yet consumed
+    /// via [`WindowInner::drain_wheel_deltas`].
+    wheel_deltas: RefCell<VecDeque<WheelDelta>>,
+    /// Button presses, with multi-click counts applied, not yet consumed
+    /// via [`WindowInner::drain_clicks`].
+    clicks: RefCell<VecDeque<MouseClick>>,
+    /// Whether this window currently holds the mouse capture, set via
+    /// [`super::Window::set_mouse_capture`] and cleared on `WM_CAPTURECHANGED`,
+    /// since capture can also be taken away involuntarily, e.g. by another
+    /// window.
+    mouse_captured: Cell<bool>,
+    /// Events posted via a [`super::UserEventSender`], not yet consumed via
+    /// [`WindowInner::drain_user_events`].
+    user_events: RefCell<VecDeque<Box<dyn Any + Send>>>,
+    /// Reconstructs a posted event's payload from its raw pointer, set via
+    /// [`WindowInner::set_user_event_reconstructor`] when
+    /// [`super::Window::register_user_events`] is called. `None` until a
+    /// user event type has been registered.
+    ///
+    /// Every `WM_USER_EVENT` message is tagged in its `wParam` with the
+    /// reconstructor its sender was built for, which may not be this one:
+    /// a message posted under one registered type can still be in flight
+    /// when a later `register_user_events` call replaces it with another.
+    /// Only messages whose tag still matches this field are queued; others
+    /// are reconstructed with their own tagged function just to run the
+    /// payload's destructor, not stored. See `WM_USER_EVENT` below.
+    user_event_reconstruct: Cell<Option<fn(isize) -> Box<dyn Any + Send>>>,
+    /// Fed every [`Event`] as the window procedure observes it, set via
+    /// [`super::Window::events`]. Only one receiver is tracked at a time;
+    /// calling it again replaces the previous channel.
+    event_sender: RefCell<Option<Sender<Event>>>,
+    /// The solid color painted by `WM_ERASEBKGND`, set via
+    /// [`super::Builder::with_background`]. `None` leaves erasing to the
+    /// default window procedure, which paints the class background (none,
+    /// by default).
+    background: Cell<Option<Color>>,
+    /// Whether a `SetTimer`-driven paint tick runs for the duration of the
+    /// modal resize/move loop, set via
+    /// [`super::Builder::with_live_resize`]. Without this, renderers that
+    /// only redraw in response to `WM_PAINT` appear frozen while the user
+    /// drags an edge, since `WM_PAINT` alone doesn't fire fast enough
+    /// inside that loop.
+    live_resize: Cell<bool>,
+    /// The most recent `WM_ENTERSIZEMOVE`/`WM_EXITSIZEMOVE` transition
+    /// (`true` for enter, `false` for exit), not yet consumed via
+    /// [`WindowInner::take_size_move_change`].
+    pending_size_move: Cell<Option<bool>>,
+}
+
+impl WindowInner {
+    pub(super) fn new(
+        theme: Theme,
+        size: Size2D<i32>,
+        handler: Option<Box<dyn WindowHandler>>,
+        min_size: Option<Size2D<i32>>,
+        max_size: Option<Size2D<i32>>,
+        decorations: bool,
+        background: Option<Color>,
+        live_resize: bool,
+    ) -> Self {
+        Self {
+            keyboard: RefCell::new(Keyboard::default()),
+            mouse: RefCell::new(Mouse::default()),
+            dpi: Cell::new(Dpi::default()),
+            theme: Cell::new(theme),
+            size: Cell::new(size),
+            requesting_close: Cell::new(false),
+            requesting_shutdown: Cell::new(false),
+            requesting_paint: Cell::new(false),
+            paint_generation: Cell::new(0),
+            pending_paint_rect: Cell::new(None),
+            pending_resize: Cell::new(None),
+            print_client_target: Cell::new(None),
+            key_transitions: RefCell::new(VecDeque::new()),
+            raw_mouse_deltas: RefCell::new(VecDeque::new()),
+            handler: RefCell::new(handler),
+            min_size: Cell::new(min_size),
+            max_size: Cell::new(max_size),
+            pending_state_change: Cell::new(None),
+            requesting_dpi_change: Cell::new(false),
+            dpi_generation: Cell::new(0),
+            requesting_theme_change: Cell::new(false),
+            theme_change_generation: Cell::new(0),
+            decorations: Cell::new(decorations),
+            drag_regions: RefCell::new(Vec::new()),
+            caption_buttons: RefCell::new(Vec::new()),
+            focused: Cell::new(false),
+            // SAFETY: `IDC_ARROW` is a built-in cursor resource that always
+            // exists.
+            cursor: Cell::new(unsafe { LoadCursorW(None, IDC_ARROW) }.unwrap_or_default()),
+            active_custom_cursor: RefCell::new(None),
+            active_icon: RefCell::new(None),
+            cursor_grab: Cell::new(CursorGrab::None),
+            menu_bar: RefCell::new(None),
+            menu_selections: RefCell::new(VecDeque::new()),
+            control_notifications: RefCell::new(VecDeque::new()),
+            saved_ime_context: Cell::new(None),
+            media_commands: RefCell::new(VecDeque::new()),
+            power_events: RefCell::new(VecDeque::new()),
+            #[cfg(feature = "device_notifications")]
+            device_notify_handles: RefCell::new(Vec::new()),
+            #[cfg(feature = "device_notifications")]
+            device_events: RefCell::new(VecDeque::new()),
+            touch_contacts: RefCell::new(VecDeque::new()),
+            gesture_recognizer: RefCell::new(GestureRecognizer::new()),
+            gestures: RefCell::new(VecDeque::new()),
+            wheel_deltas: RefCell::new(VecDeque::new()),
+            clicks: RefCell::new(VecDeque::new()),
+            mouse_captured: Cell::new(false),
+            user_events: RefCell::new(VecDeque::new()),
+            user_event_reconstruct: Cell::new(None),
+            event_sender: RefCell::new(None),
+            background: Cell::new(background),
+            live_resize: Cell::new(live_resize),
+            pending_size_move: Cell::new(None),
+        }
+    }
+
+    /// Returns `true` if the window currently has keyboard focus.
+    pub(super) fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    /// Sets the cursor shown over the client area to a built-in shape,
+    /// releasing any previously active [`CustomCursor`].
+    pub(super) fn set_cursor(&self, cursor: HCURSOR) {
+        self.cursor.set(cursor);
+        *self.active_custom_cursor.borrow_mut() = None;
+    }
+
+    /// Sets the cursor shown over the client area to `cursor`, keeping it
+    /// alive for as long as it remains active.
+    pub(super) fn set_custom_cursor(&self, cursor: CustomCursor) {
+        self.cursor.set(cursor.handle());
+        *self.active_custom_cursor.borrow_mut() = Some(cursor);
+    }
+
+    /// Sets `icon` as both the window's title bar/taskbar icon and its
+    /// `Alt+Tab` icon, via `WM_SETICON`, keeping it alive for as long as it
+    /// remains active.
+    pub(super) fn set_icon(&self, hwnd: HWND, icon: Icon) {
+        let handle = icon.handle();
+        // SAFETY: `hwnd` is a valid, live window and `handle` stays alive
+        // for at least as long as `icon` is stored below.
+        unsafe {
+            SendMessageW(
+                hwnd,
+                WM_SETICON,
+                Some(WPARAM(ICON_SMALL as usize)),
+                Some(LPARAM(handle.0 as isize)),
+            );
+            SendMessageW(
+                hwnd,
+                WM_SETICON,
+                Some(WPARAM(ICON_BIG as usize)),
+                Some(LPARAM(handle.0 as isize)),
+            );
+        }
+        *self.active_icon.borrow_mut() = Some(icon);
+    }
+
+    /// Sets and immediately applies the cursor confinement mode.
+    pub(super) fn set_cursor_grab(&self, hwnd: HWND, grab: CursorGrab) -> Result<()> {
+        self.cursor_grab.set(grab);
+        self.apply_cursor_grab(hwnd)
+    }
+
+    /// Re-applies the current cursor confinement mode, e.g. after Windows
+    /// releases the clip on focus loss.
+    fn apply_cursor_grab(&self, hwnd: HWND) -> Result<()> {
+        match self.cursor_grab.get() {
+            CursorGrab::None => {
+                // SAFETY: releasing the cursor clip is always valid.
+                unsafe { ClipCursor(None) }.map_err(Error::from)
+            }
+            CursorGrab::Confined => {
+                let rect = client_rect_in_screen(hwnd)?;
+                // SAFETY: `rect` is a valid, fully-initialized `RECT` for
+                // the duration of the call.
+                unsafe { ClipCursor(Some(&rect)) }.map_err(Error::from)
+            }
+            CursorGrab::Locked => {
+                let client = client_rect_in_screen(hwnd)?;
+                let x = (client.left + client.right) / 2;
+                let y = (client.top + client.bottom) / 2;
+                // Clip to a single point at the client area's center, so
+                // the OS cursor stops moving while raw input deltas (see
+                // `enable_raw_input`) keep reporting relative motion.
+                let locked = RECT {
+                    left: x,
+                    top: y,
+                    right: x + 1,
+                    bottom: y + 1,
+                };
+                // SAFETY: `locked` is a valid, fully-initialized `RECT` for
+                // the duration of the call.
+                unsafe { ClipCursor(Some(&locked)) }.map_err(Error::from)
+            }
+        }
+    }
+
+    /// Captures or releases the mouse, so drag interactions keep receiving
+    /// button-up and move messages even once the cursor leaves the client
+    /// area.
+    pub(super) fn set_mouse_capture(&self, hwnd: HWND, capture: bool) -> Result<()> {
+        if capture {
+            // SAFETY: `hwnd` is a valid, live window. `SetCapture` cannot
+            // fail.
+            unsafe { SetCapture(hwnd) };
+        } else {
+            // SAFETY: releasing the mouse capture is always valid.
+            unsafe { ReleaseCapture() }.map_err(Error::from)?;
+        }
+        self.mouse_captured.set(capture);
+        Ok(())
+    }
+
+    /// Returns `true` if this window currently holds the mouse capture.
+    pub(super) fn has_mouse_capture(&self) -> bool {
+        self.mouse_captured.get()
+    }
+
+    /// Removes and returns the most recent minimize/maximize/restore
+    /// transition reported by `WM_SIZE`, if any.
+    pub(super) fn take_state_change(&self) -> Option<WindowState> {
+        self.pending_state_change.take()
+    }
+
+    /// Removes and returns the most recent `WM_ENTERSIZEMOVE`/
+    /// `WM_EXITSIZEMOVE` transition (`true` for enter, `false` for exit),
+    /// if any.
+    pub(super) fn take_size_move_change(&self) -> Option<bool> {
+        self.pending_size_move.take()
+    }
+
+    /// Returns `true` if the window has received at least one
+    /// `WM_DPICHANGED`.
+    pub(super) fn is_requesting_dpi_change(&self) -> bool {
+        self.requesting_dpi_change.get()
+    }
+
+    /// Bumped on every `WM_DPICHANGED`, used by [`crate::event_loop`] to
+    /// detect DPI changes.
+    pub(super) fn dpi_generation(&self) -> u64 {
+        self.dpi_generation.get()
+    }
+
+    /// Returns `true` if the system theme or high-contrast setting has
+    /// changed since the window was created.
+    pub(super) fn is_requesting_theme_change(&self) -> bool {
+        self.requesting_theme_change.get()
+    }
+
+    /// Bumped on every system theme/high-contrast change, used by
+    /// [`crate::event_loop`] to detect changes.
+    pub(super) fn theme_change_generation(&self) -> u64 {
+        self.theme_change_generation.get()
+    }
+
+    /// Sets the smallest outer window size, frame included, the user may
+    /// resize the window to, or `None` to remove the constraint.
+    pub(super) fn set_min_size(&self, size: Option<Size2D<i32>>) {
+        self.min_size.set(size);
+    }
+
+    /// Sets the largest outer window size, frame included, the user may
+    /// resize the window to, or `None` to remove the constraint.
+    pub(super) fn set_max_size(&self, size: Option<Size2D<i32>>) {
+        self.max_size.set(size);
+    }
+
+    /// Removes and returns the most recent size reported by `WM_SIZE`, if
+    /// the window has been resized since the last call.
+    pub(super) fn take_resize(&self) -> Option<Size2D<i32>> {
+        self.pending_resize.take()
+    }
+
+    /// Removes and returns the invalid region from the most recent
+    /// `WM_PAINT`, in client coordinates, if the window has an outstanding
+    /// paint request.
+    pub(super) fn take_paint_request(&self) -> Option<Rect2D<i32>> {
+        self.pending_paint_rect.take()
+    }
+
+    /// The HDC supplied by the `WM_PRINTCLIENT` currently being handled, if
+    /// any.
+    pub(super) fn print_client_target(&self) -> Option<HDC> {
+        self.print_client_target.get()
+    }
+
+    /// Replaces the registered title-bar drag regions.
+    pub(super) fn set_drag_regions(&self, regions: Vec<Rect2D<i32>>) {
+        *self.drag_regions.borrow_mut() = regions;
+    }
+
+    /// Replaces the registered caption button regions.
+    pub(super) fn set_caption_buttons(&self, buttons: Vec<(CaptionButton, Rect2D<i32>)>) {
+        *self.caption_buttons.borrow_mut() = buttons;
+    }
+
+    /// Resolves a `WM_NCHITTEST` screen-space point against the registered
+    /// caption button and drag regions, falling back to `HTCLIENT`.
+    fn hit_test(&self, hwnd: HWND, lparam: LPARAM) -> u32 {
+        let mut point = POINT {
+            x: (lparam.0 & 0xffff) as i16 as i32,
+            y: ((lparam.0 >> 16) & 0xffff) as i16 as i32,
+        };
+        // SAFETY: `point` is a valid, initialized in/out parameter and
+        // `hwnd` is the window currently receiving this message.
+        if unsafe { ScreenToClient(hwnd, &mut point) }.as_bool() {
+            let point = Point2D::new(point.x, point.y);
+
+            for (button, region) in self.caption_buttons.borrow().iter() {
+                if contains(*region, point) {
+                    return match button {
+                        CaptionButton::Minimize => HTMINBUTTON,
+                        CaptionButton::Maximize => HTMAXBUTTON,
+                        CaptionButton::Close => HTCLOSE,
+                    };
+                }
+            }
+
+            if self
+                .drag_regions
+                .borrow()
+                .iter()
+                .any(|region| contains(*region, point))
+            {
+                return HTCAPTION;
+            }
+        }
+
+        HTCLIENT
+    }
+
+    /// Removes and returns all key transitions accumulated since the last
+    /// call.
+    pub(super) fn drain_key_transitions(&self) -> Vec<(KeyCode, bool)> {
+        self.key_transitions.borrow_mut().drain(..).collect()
+    }
+
+    /// Attaches `menu` as the window's menu bar, replacing and discarding
+    /// any previously attached menu, and keeps it alive for as long as it
+    /// remains attached.
+    pub(super) fn set_menu_bar(&self, hwnd: HWND, menu: Menu) -> Result<()> {
+        // SAFETY: `hwnd` is a valid, live window and `menu.handle()` is a
+        // valid menu; `SetMenu` takes ownership of attaching it, but
+        // `menu` itself must still be kept alive and destroyed by us.
+        unsafe { SetMenu(hwnd, Some(menu.handle())) }.map_err(Error::from)?;
+        *self.menu_bar.borrow_mut() = Some(menu);
+        Ok(())
+    }
+
+    /// Removes and returns all menu selections accumulated since the last
+    /// call.
+    pub(super) fn drain_menu_selections(&self) -> Vec<u32> {
+        self.menu_selections.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all control notifications accumulated since the
+    /// last call.
+    pub(super) fn drain_control_notifications(&self) -> Vec<(u32, u32)> {
+        self.control_notifications.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all media/browser commands accumulated since the
+    /// last call.
+    pub(super) fn drain_media_commands(&self) -> Vec<MediaCommand> {
+        self.media_commands.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all power/battery notifications accumulated
+    /// since the last call.
+    pub(super) fn drain_power_events(&self) -> Vec<PowerEvent> {
+        self.power_events.borrow_mut().drain(..).collect()
+    }
+
+    /// Registers `hwnd` for `WM_DEVICECHANGE` notifications about each of
+    /// `classes`, so their arrivals/removals start showing up in
+    /// [`WindowInner::drain_device_events`].
+    #[cfg(feature = "device_notifications")]
+    pub(super) fn register_device_notifications(
+        &self,
+        hwnd: HWND,
+        classes: &[DeviceClass],
+    ) -> Result<()> {
+        for &class in classes {
+            let filter = DEV_BROADCAST_DEVICEINTERFACE_W {
+                dbcc_size: std::mem::size_of::<DEV_BROADCAST_DEVICEINTERFACE_W>() as u32,
+                dbcc_devicetype: DBT_DEVTYP_DEVICEINTERFACE.0,
+                dbcc_classguid: class.guid(),
+                ..Default::default()
+            };
+            // SAFETY: `filter` is a valid, initialized
+            // `DEV_BROADCAST_DEVICEINTERFACE_W` for the duration of the
+            // call.
+            let handle = unsafe {
+                RegisterDeviceNotificationW(
+                    HANDLE::from(hwnd),
+                    &filter as *const _ as *const core::ffi::c_void,
+                    DEVICE_NOTIFY_WINDOW_HANDLE,
+                )
+            }
+            .map_err(Error::from)?;
+            self.device_notify_handles.borrow_mut().push(handle);
+        }
+        Ok(())
+    }
+
+    /// Removes and returns all device arrival/removal notifications
+    /// accumulated since the last call.
+    #[cfg(feature = "device_notifications")]
+    pub(super) fn drain_device_events(&self) -> Vec<(DeviceClass, DeviceEvent)> {
+        self.device_events.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all touch contacts accumulated since the last
+    /// call.
+    pub(super) fn drain_touch_contacts(&self) -> Vec<TouchContact> {
+        self.touch_contacts.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all two-finger pinch/rotate/pan gestures
+    /// accumulated since the last call.
+    pub(super) fn drain_gestures(&self) -> Vec<Gesture> {
+        self.gestures.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all mouse-wheel deltas accumulated since the
+    /// last call.
+    pub(super) fn drain_wheel_deltas(&self) -> Vec<WheelDelta> {
+        self.wheel_deltas.borrow_mut().drain(..).collect()
+    }
+
+    /// Removes and returns all button clicks accumulated since the last
+    /// call.
+    pub(super) fn drain_clicks(&self) -> Vec<MouseClick> {
+        self.clicks.borrow_mut().drain(..).collect()
+    }
+
+    /// Registers `reconstruct` as the function used to recover a boxed
+    /// event payload from a `WM_USER_EVENT` message's `lParam`. Only one
+    /// reconstructor is tracked at a time; registering again replaces it.
+    pub(super) fn set_user_event_reconstructor(
+        &self,
+        reconstruct: fn(isize) -> Box<dyn Any + Send>,
+    ) {
+        self.user_event_reconstruct.set(Some(reconstruct));
+    }
+
+    /// Removes and returns all user events accumulated since the last call.
+    pub(super) fn drain_user_events(&self) -> Vec<Box<dyn Any + Send>> {
+        self.user_events.borrow_mut().drain(..).collect()
+    }
+
+    /// Registers `sender` to receive every [`Event`] the window procedure
+    /// observes from now on. Only one channel is tracked at a time;
+    /// registering again replaces it.
+    pub(super) fn set_event_sender(&self, sender: Sender<Event>) {
+        *self.event_sender.borrow_mut() = Some(sender);
+    }
+
+    /// Forwards `event` to the channel registered via
+    /// [`WindowInner::set_event_sender`], if any. Silently drops the event
+    /// if the receiver has been dropped.
+    fn send_event(&self, event: Event) {
+        if let Some(sender) = self.event_sender.borrow().as_ref() {
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Enables or disables text input: while disabled, `WM_CHAR` stops
+    /// accumulating into the keyboard's input buffer and the window's IME is
+    /// detached, so held-down "action" keys can't trigger IME composition.
+    /// Intended for games that toggle between gameplay and a chat box.
+    pub(super) fn set_text_input_enabled(&self, hwnd: HWND, enabled: bool) {
+        self.keyboard.borrow_mut().set_text_input_enabled(enabled);
+
+        if enabled {
+            if let Some(context) = self.saved_ime_context.take() {
+                // SAFETY: `hwnd` is a valid, live window and `context` is
+                // the context previously detached from it.
+                unsafe {
+                    ImmAssociateContext(hwnd, context);
+                }
+            }
+        } else if self.saved_ime_context.get().is_none() {
+            // SAFETY: `hwnd` is a valid, live window; associating a null
+            // context detaches the IME and returns the previous one.
+            let previous = unsafe { ImmAssociateContext(hwnd, HIMC(std::ptr::null_mut())) };
+            self.saved_ime_context.set(Some(previous));
+        }
+    }
+
+    /// Registers the window to receive `WM_INPUT` mouse motion.
+    pub(super) fn enable_raw_input(&self, hwnd: HWND) -> Result<()> {
+        let device = RAWINPUTDEVICE {
+            usUsagePage: 0x01, // Generic Desktop Controls
+            usUsage: 0x02,     // Mouse
+            dwFlags: RIDEV_INPUTSINK,
+            hwndTarget: hwnd,
+        };
+
+        // SAFETY: `device` is a single, fully-initialized `RAWINPUTDEVICE`
+        // and its size matches the slice passed.
+        unsafe {
+            RegisterRawInputDevices(&[device], std::mem::size_of::<RAWINPUTDEVICE>() as u32)
+                .map_err(Error::from)
+        }
+    }
+
+    /// Removes and returns all raw mouse deltas accumulated since the last
+    /// call.
+    pub(super) fn drain_raw_mouse_deltas(&self) -> Vec<(i32, i32)> {
+        self.raw_mouse_deltas.borrow_mut().drain(..).collect()
+    }
+
+    /// Handles a single `WM_INPUT` message, appending any relative mouse
+    /// motion it carries to the raw-delta queue.
+    fn handle_raw_input(&self, lparam: LPARAM) {
+        let mut raw = RAWINPUT::default();
+        let mut size = std::mem::size_of::<RAWINPUT>() as u32;
+
+        // SAFETY: `raw` is large enough to hold any `RAWINPUT` payload and
+        // `size` reflects its true size; `GetRawInputData` writes at most
+        // `size` bytes into it.
+        let written = unsafe {
+            GetRawInputData(
+                HRAWINPUT(lparam.0),
+                RID_INPUT,
+                Some(std::ptr::addr_of_mut!(raw).cast()),
+                &mut size,
+                std::mem::size_of::<RAWINPUTHEADER>() as u32,
+            )
+        };
+
+        if written == u32::MAX || raw.header.dwType != RIM_TYPEMOUSE.0 {
+            return;
+        }
+
+        // SAFETY: `dwType` was just checked to be `RIM_TYPEMOUSE`, so the
+        // `mouse` variant of the `data` union is the active one.
+        let mouse = unsafe { raw.data.mouse };
+        if mouse.usFlags as u32 & MOUSE_MOVE_ABSOLUTE.0 == 0 {
+            self.raw_mouse_deltas
+                .borrow_mut()
+                .push_back((mouse.lLastX, mouse.lLastY));
+        }
+    }
+
+    /// Handles a single window message, returning `Some` if the message was
+    /// fully handled and the default window procedure should be skipped.
+    pub(super) fn handle_message(
+        &self,
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> Option<LRESULT> {
+        let handle = WindowHandle::new(hwnd, self);
+        if let Some(handler) = self.handler.borrow_mut().as_mut() {
+            handler.on_raw_message(handle, msg, wparam, lparam);
+        }
+
+        match msg {
+            WM_CLOSE => {
+                let response = self
+                    .handler
+                    .borrow_mut()
+                    .as_mut()
+                    .map_or(CloseResponse::Allow, |handler| handler.on_close(handle));
+                if response == CloseResponse::Allow {
+                    self.requesting_close.set(true);
+                    self.send_event(Event::CloseRequested);
+                }
+                Some(LRESULT(0))
+            }
+            WM_QUERYENDSESSION => {
+                let response = self
+                    .handler
+                    .borrow_mut()
+                    .as_mut()
+                    .map_or(CloseResponse::Allow, |handler| {
+                        handler.on_shutdown_requested(handle)
+                    });
+                if response == CloseResponse::Allow {
+                    self.requesting_shutdown.set(true);
+                    self.send_event(Event::ShutdownRequested);
+                }
+                Some(LRESULT(if response == CloseResponse::Allow {
+                    1
+                } else {
+                    0
+                }))
+            }
+            WM_ENDSESSION => {
+                if wparam.0 == 0 {
+                    // The shutdown was canceled, e.g. another application
+                    // vetoed it; clear the flag set above so the app
+                    // doesn't act as though a shutdown is still pending.
+                    self.requesting_shutdown.set(false);
+                }
+                None
+            }
+            WM_DESTROY => {
+                // Any `WM_USER_EVENT` messages already queued for this
+                // `hwnd` via `UserEventSender::post` will never be
+                // delivered now, since `hwnd` becomes invalid once
+                // `DestroyWindow` returns; reclaim and drop their boxed
+                // payloads here instead of leaking them.
+                let mut msg = MSG::default();
+                // SAFETY: `msg` is a valid out-parameter for the duration
+                // of the call; `hwnd` is still valid while handling its own
+                // `WM_DESTROY`.
+                while unsafe {
+                    PeekMessageW(
+                        &mut msg,
+                        Some(hwnd),
+                        WM_USER_EVENT,
+                        WM_USER_EVENT,
+                        PM_REMOVE,
+                    )
+                }
+                .as_bool()
+                {
+                    // SAFETY: as in the `WM_USER_EVENT` arm below,
+                    // `msg.wParam.0` is always the reconstructor
+                    // `UserEventSender::post` tagged this message with,
+                    // which is the only function that can safely recover
+                    // this particular payload's type and run its
+                    // destructor, regardless of what's currently
+                    // registered.
+                    let tagged: fn(isize) -> Box<dyn Any + Send> =
+                        unsafe { std::mem::transmute(msg.wParam.0) };
+                    tagged(msg.lParam.0);
+                }
+                #[cfg(feature = "device_notifications")]
+                for handle in self.device_notify_handles.borrow_mut().drain(..) {
+                    // SAFETY: `handle` was returned by a prior
+                    // `RegisterDeviceNotificationW` call and not yet
+                    // unregistered.
+                    let _ = unsafe { UnregisterDeviceNotification(handle) };
+                }
+
+                if LIVE_WINDOWS.fetch_sub(1, Ordering::SeqCst) == 1 {
+                    // SAFETY: posting a quit message is always valid.
+                    unsafe {
+                        PostQuitMessage(0);
+                    }
+                }
+                None
+            }
+            WM_PAINT => {
+                // Capture the invalid region before the handler has a
+                // chance to validate it via `Window::begin_paint`.
+                let mut rect = RECT::default();
+                // SAFETY: `rect` is a valid out-parameter for the duration
+                // of the call; `false` leaves the update region untouched
+                // so `DefWindowProcW`'s own `BeginPaint` still sees it.
+                if unsafe { GetUpdateRect(hwnd, Some(&mut rect), false) }.as_bool() {
+                    self.pending_paint_rect.set(Some(rect.into()));
+                }
+
+                // Record the paint request and fall through to the default
+                // window procedure, which validates the update region via
+                // its own `BeginPaint`/`EndPaint` if the handler doesn't
+                // call `Window::begin_paint` itself.
+                self.requesting_paint.set(true);
+                self.paint_generation.set(self.paint_generation.get() + 1);
+                if let Some(handler) = self.handler.borrow_mut().as_mut() {
+                    handler.on_paint(handle);
+                }
+                self.send_event(Event::Paint);
+                None
+            }
+            WM_PRINTCLIENT => {
+                // SAFETY: `WM_PRINTCLIENT` guarantees `wparam` holds the
+                // HDC to paint into, unlike `WM_PAINT`, which supplies none
+                // and expects `BeginPaint` to retrieve one.
+                let hdc = HDC(wparam.0 as *mut _);
+                self.print_client_target.set(Some(hdc));
+                if let Some(handler) = self.handler.borrow_mut().as_mut() {
+                    handler.on_paint(handle);
+                }
+                self.send_event(Event::Paint);
+                self.print_client_target.set(None);
+                Some(LRESULT(0))
+            }
+            WM_ERASEBKGND => {
+                let color = self.background.get()?;
+
+                let mut rect = RECT::default();
+                // SAFETY: `hwnd` is a valid, live window and `rect` is a
+                // valid out-parameter for the duration of the call.
+                if unsafe { GetClientRect(hwnd, &mut rect) }.is_err() {
+                    return None;
+                }
+
+                // SAFETY: `WM_ERASEBKGND` guarantees `wparam` holds the HDC
+                // to erase.
+                let hdc = HDC(wparam.0 as *mut _);
+                // SAFETY: `color.to_colorref()` packs into a valid `COLORREF`.
+                let brush = unsafe { CreateSolidBrush(COLORREF(color.to_colorref())) };
+                // SAFETY: `hdc` is the valid HDC from `wparam` above and
+                // `rect` is fully initialized; `brush` was just created.
+                unsafe {
+                    FillRect(hdc, &rect, brush);
+                    let _ = DeleteObject(brush.into());
+                }
+                Some(LRESULT(1))
+            }
+            WM_SIZE => {
+                let width = (lparam.0 & 0xffff) as i16 as i32;
+                let height = ((lparam.0 >> 16) & 0xffff) as i16 as i32;
+                let size = Size2D::new(width, height);
+                self.size.set(size);
+                self.pending_resize.set(Some(size));
+                self.send_event(Event::Resized(size));
+
+                let state = match wparam.0 as u32 {
+                    SIZE_MINIMIZED => Some(WindowState::Minimized),
+                    SIZE_MAXIMIZED => Some(WindowState::Maximized),
+                    SIZE_RESTORED => Some(WindowState::Normal),
+                    _ => None,
+                };
+                if let Some(state) = state {
+                    self.pending_state_change.set(Some(state));
+                    self.send_event(Event::StateChanged(state));
+                }
+                None
+            }
+            WM_INPUT => {
+                self.handle_raw_input(lparam);
+                None
+            }
+            WM_ENTERSIZEMOVE => {
+                if self.live_resize.get() {
+                    // SAFETY: `hwnd` is a valid, live window.
+                    unsafe {
+                        SetTimer(Some(hwnd), LIVE_RESIZE_TIMER_ID, LIVE_RESIZE_TICK_MS, None);
+                    }
+                }
+                self.pending_size_move.set(Some(true));
+                self.send_event(Event::EnterSizeMove);
+                None
+            }
+            WM_EXITSIZEMOVE => {
+                if self.live_resize.get() {
+                    // SAFETY: `hwnd` is a valid, live window; if no timer
+                    // with this ID exists, `KillTimer` fails harmlessly.
+                    let _ = unsafe { KillTimer(Some(hwnd), LIVE_RESIZE_TIMER_ID) };
+                }
+                self.pending_size_move.set(Some(false));
+                self.send_event(Event::ExitSizeMove);
+                None
+            }
+            WM_TIMER => {
+                if wparam.0 == LIVE_RESIZE_TIMER_ID {
+                    // SAFETY: `hwnd` is a valid, live window; invalidating
+                    // without erasing avoids the background flashing on
+                    // every tick.
+                    unsafe {
+                        let _ = InvalidateRect(Some(hwnd), None, false);
+                    }
+                    Some(LRESULT(0))
+                } else {
+                    None
+                }
+            }
+            WM_SETFOCUS => {
+                self.focused.set(true);
+                // Windows automatically releases any cursor clip when the
+                // window loses focus, so it must be re-applied here.
+                let _ = self.apply_cursor_grab(hwnd);
+                // Toggle-key state may have changed while the window
+                // wasn't focused to observe the key events that track it.
+                self.keyboard.borrow_mut().sync_toggle_keys();
+                self.send_event(Event::FocusChanged(true));
+                None
+            }
+            WM_KILLFOCUS => {
+                self.focused.set(false);
+                // Avoid stuck-down keys when the window loses focus
+                // mid-keystroke, e.g. alt-tabbing away.
+                self.keyboard.borrow_mut().reset();
+                self.send_event(Event::FocusChanged(false));
+                None
+            }
+            WM_DPICHANGED => {
+                let new_dpi = (wparam.0 & 0xffff) as u32;
+                self.dpi.set(Dpi::from_raw(new_dpi));
+                self.requesting_dpi_change.set(true);
+                self.dpi_generation.set(self.dpi_generation.get() + 1);
+                self.send_event(Event::DpiChanged(self.dpi.get()));
+
+                // SAFETY: for `WM_DPICHANGED`, `lparam` always points to a
+                // valid `RECT` suggesting the window's new screen position
+                // and size at the new DPI.
+                let suggested = unsafe { &*(lparam.0 as *const RECT) };
+                // SAFETY: `hwnd` is the window currently receiving this
+                // message, so it is a valid, live window.
+                unsafe {
+                    let _ = SetWindowPos(
+                        hwnd,
+                        None,
+                        suggested.left,
+                        suggested.top,
+                        suggested.right - suggested.left,
+                        suggested.bottom - suggested.top,
+                        SWP_NOZORDER | SWP_NOACTIVATE,
+                    );
+                }
+                Some(LRESULT(0))
+            }
+            WM_SETTINGCHANGE => {
+                let setting = setting_change_name(lparam);
+
+                if matches!(
+                    setting.as_deref(),
+                    Some("ImmersiveColorSet") | Some("HighContrast")
+                ) {
+                    self.requesting_theme_change.set(true);
+                    self.theme_change_generation
+                        .set(self.theme_change_generation.get() + 1);
+                    self.send_event(Event::ThemeChanged);
+                }
+
+                if self.theme.get() == Theme::FollowSystem
+                    && setting.as_deref() == Some("ImmersiveColorSet")
+                {
+                    self.theme.get().apply(hwnd);
+                }
+                None
+            }
+            WM_NCCALCSIZE => {
+                if wparam.0 != 0 && !self.decorations.get() {
+                    // Leave the proposed client rect untouched so it fills
+                    // the whole window, eliminating the caption and resize
+                    // border while keeping `WS_OVERLAPPEDWINDOW` so
+                    // snapping, shadows, and Aero animations still work.
+                    Some(LRESULT(0))
+                } else {
+                    None
+                }
+            }
+            WM_NCHITTEST => {
+                if self.decorations.get() {
+                    None
+                } else {
+                    Some(LRESULT(self.hit_test(hwnd, lparam) as isize))
+                }
+            }
+            WM_SETCURSOR => {
+                if (lparam.0 as u32 & 0xffff) == HTCLIENT {
+                    // SAFETY: `self.cursor` always holds a live cursor: a
+                    // built-in one from `LoadCursorW`, or the `CustomCursor`
+                    // kept alive in `active_custom_cursor`.
+                    unsafe {
+                        SetCursor(Some(self.cursor.get()));
+                    }
+                    Some(LRESULT(1))
+                } else {
+                    None
+                }
+            }
+            WM_GETMINMAXINFO => {
+                // SAFETY: for `WM_GETMINMAXINFO`, `lparam` always points to a
+                // valid `MINMAXINFO` that we are expected to fill in.
+                let info = unsafe { &mut *(lparam.0 as *mut MINMAXINFO) };
+                if let Some(min) = self.min_size.get() {
+                    info.ptMinTrackSize = POINT {
+                        x: min.width,
+                        y: min.height,
+                    };
+                }
+                if let Some(max) = self.max_size.get() {
+                    info.ptMaxTrackSize = POINT {
+                        x: max.width,
+                        y: max.height,
+                    };
+                }
+                Some(LRESULT(0))
+            }
+            WM_KEYDOWN | WM_KEYUP => {
+                if let Some(evt) = KeyEvent::new(msg, wparam, lparam) {
+                    self.key_transitions
+                        .borrow_mut()
+                        .push_back((evt.code, evt.pressed));
+                    if let Some(handler) = self.handler.borrow_mut().as_mut() {
+                        handler.on_key(handle, evt.code, evt.pressed);
+                    }
+                    self.send_event(Event::Key {
+                        code: evt.code,
+                        pressed: evt.pressed,
+                    });
+                }
+                self.keyboard.borrow_mut().process_evt(msg, wparam, lparam);
+                None
+            }
+            WM_CHAR => {
+                self.keyboard.borrow_mut().process_evt(msg, wparam, lparam);
+                None
+            }
+            WM_UNICHAR => {
+                self.keyboard.borrow_mut().process_evt(msg, wparam, lparam);
+                // Answering a `UNICODE_NOCHAR` probe with a non-zero result
+                // advertises `WM_UNICHAR` support; the return value is
+                // otherwise ignored.
+                if wparam.0 as u32 == UNICODE_NOCHAR {
+                    Some(LRESULT(1))
+                } else {
+                    None
+                }
+            }
+            WM_MOUSEMOVE | WM_LBUTTONDOWN | WM_LBUTTONUP | WM_RBUTTONDOWN | WM_RBUTTONUP
+            | WM_MBUTTONDOWN | WM_MBUTTONUP => {
+                if let Some(click) = self.mouse.borrow_mut().process_evt(msg, wparam, lparam) {
+                    self.clicks.borrow_mut().push_back(click);
+                }
+                None
+            }
+            WM_CAPTURECHANGED => {
+                self.mouse_captured.set(false);
+                None
+            }
+            WM_XBUTTONDOWN | WM_XBUTTONUP => {
+                if let Some(click) = self.mouse.borrow_mut().process_evt(msg, wparam, lparam) {
+                    self.clicks.borrow_mut().push_back(click);
+                }
+                // An application should return `TRUE` (non-zero) from
+                // `WM_XBUTTONDOWN`/`WM_XBUTTONUP` if it handles the message.
+                Some(LRESULT(1))
+            }
+            WM_USER_EVENT => {
+                // SAFETY: `wparam.0` is always a
+                // `fn(isize) -> Box<dyn Any + Send>` previously cast to
+                // `usize` by `UserEventSender::post`; function pointers
+                // round-trip through `usize` unchanged.
+                let tagged: fn(isize) -> Box<dyn Any + Send> =
+                    unsafe { std::mem::transmute(wparam.0) };
+                let event = tagged(lparam.0);
+                // Only keep the event if `tagged` is still the currently
+                // registered reconstructor — otherwise this message was
+                // posted under a type that a later `register_user_events`
+                // call has since replaced, and `event` is some other `T`
+                // masquerading as `dyn Any`; drop it instead of queuing it
+                // for `drain_user_events::<U>` to wrongly downcast.
+                if self.user_event_reconstruct.get().map(|f| f as usize) == Some(wparam.0) {
+                    self.user_events.borrow_mut().push_back(event);
+                }
+                None
+            }
+            WM_MOUSEWHEEL | WM_MOUSEHWHEEL => {
+                self.wheel_deltas
+                    .borrow_mut()
+                    .push_back(Mouse::process_wheel(msg, wparam));
+                None
+            }
+            WM_COMMAND => {
+                // Menu (high word 0) and accelerator (high word 1)
+                // selections leave `lParam` zero. Anything else is a
+                // notification: a control's carries its `HWND` in `lParam`
+                // (so `BN_CLICKED`, also 0, still counts), and a thumbnail
+                // toolbar click carries `THBN_CLICKED` in the high word.
+                let notification = (wparam.0 >> 16) as u32;
+                let id = (wparam.0 & 0xffff) as u32;
+                if lparam.0 == 0 && notification <= 1 {
+                    self.menu_selections.borrow_mut().push_back(id);
+                } else {
+                    self.control_notifications
+                        .borrow_mut()
+                        .push_back((id, notification));
+                    if let Some(handler) = self.handler.borrow_mut().as_mut() {
+                        handler.on_control_event(handle, id, notification);
+                    }
+                }
+                None
+            }
+            WM_APPCOMMAND => match MediaCommand::from_lparam(lparam) {
+                Some(command) => {
+                    self.media_commands.borrow_mut().push_back(command);
+                    // A non-zero result tells the system the command was
+                    // handled, per the `WM_APPCOMMAND` contract.
+                    Some(LRESULT(1))
+                }
+                None => None,
+            },
+            WM_POWERBROADCAST => {
+                if let Some(event) = PowerEvent::from_wparam(wparam) {
+                    self.power_events.borrow_mut().push_back(event);
+                }
+                None
+            }
+            #[cfg(feature = "device_notifications")]
+            WM_DEVICECHANGE => {
+                let event = match wparam.0 as u32 {
+                    DBT_DEVICEARRIVAL => Some(DeviceEvent::Arrived),
+                    DBT_DEVICEREMOVECOMPLETE => Some(DeviceEvent::Removed),
+                    _ => None,
+                };
+                if let Some(event) = event {
+                    // SAFETY: `lparam` holds a valid `DEV_BROADCAST_HDR*`
+                    // for the duration of this message, per the
+                    // `WM_DEVICECHANGE` contract for these codes.
+                    let header = unsafe { &*(lparam.0 as *const DEV_BROADCAST_HDR) };
+                    if header.dbch_devicetype == DBT_DEVTYP_DEVICEINTERFACE {
+                        // SAFETY: `dbch_devicetype` just confirmed `header`
+                        // actually points to a
+                        // `DEV_BROADCAST_DEVICEINTERFACE_W`.
+                        let interface =
+                            unsafe { &*(lparam.0 as *const DEV_BROADCAST_DEVICEINTERFACE_W) };
+                        if let Some(class) = DeviceClass::from_guid(interface.dbcc_classguid) {
+                            self.device_events.borrow_mut().push_back((class, event));
+                        }
+                    }
+                }
+                None
+            }
+            WM_POINTERDOWN | WM_POINTERUPDATE | WM_POINTERUP => {
+                if let Some(contact) = TouchContact::from_message(hwnd, msg, wparam) {
+                    if let Some(gesture) = self.gesture_recognizer.borrow_mut().update(contact) {
+                        self.gestures.borrow_mut().push_back(gesture);
+                    }
+                    self.touch_contacts.borrow_mut().push_back(contact);
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The client area's bounding rectangle, in screen coordinates, suitable
+/// for passing to `ClipCursor`.
+fn client_rect_in_screen(hwnd: HWND) -> Result<RECT> {
+    let mut rect = RECT::default();
+    // SAFETY: `rect` is a valid out-parameter and `hwnd` is a valid, live
+    // window.
+    unsafe { GetClientRect(hwnd, &mut rect) }.map_err(Error::from)?;
+
+    let mut top_left = POINT {
+        x: rect.left,
+        y: rect.top,
+    };
+    let mut bottom_right = POINT {
+        x: rect.right,
+        y: rect.bottom,
+    };
+    // SAFETY: both points are valid in/out parameters and `hwnd` is a
+    // valid, live window.
+    unsafe {
+        ClientToScreen(hwnd, &mut top_left);
+        ClientToScreen(hwnd, &mut bottom_right);
+    }
+
+    Ok(RECT {
+        left: top_left.x,
+        top: top_left.y,
+        right: bottom_right.x,
+        bottom: bottom_right.y,
+    })
+}
+
+/// Returns `true` if `point` falls within `rect`.
+fn contains(rect: Rect2D<i32>, point: Point2D<i32>) -> bool {
+    point.x >= rect.origin.x
+        && point.x < rect.origin.x + rect.size.width
+        && point.y >= rect.origin.y
+        && point.y < rect.origin.y + rect.size.height
+}
+
+/// Returns the setting name a `WM_SETTINGCHANGE` message's `lparam` points
+/// to (e.g. `"ImmersiveColorSet"` or `"HighContrast"`), if any.
+fn setting_change_name(lparam: LPARAM) -> Option<String> {
+    if lparam.0 == 0 {
+        return None;
+    }
+
+    // SAFETY: a non-zero `lparam` for `WM_SETTINGCHANGE` points to a valid,
+    // null-terminated string naming the setting that changed.
+    unsafe { PCWSTR(lparam.0 as *const u16).to_string() }.ok()
+}
+
+/// Recovers the [`WindowInner`] stashed in `hwnd`'s `GWLP_USERDATA` slot, if
+/// any (it is absent for messages dispatched before `WM_NCCREATE`).
+unsafe fn inner_for(hwnd: HWND) -> Option<*const WindowInner> {
+    let ptr = GetWindowLongPtrW(hwnd, GWLP_USERDATA) as *const WindowInner;
+    if ptr.is_null() {
+        None
+    } else {
+        Some(ptr)
+    }
+}
+
+/// The shared window procedure for every skylight-managed window.
+pub(super) unsafe extern "system" fn wnd_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_NCCREATE {
+        let create_struct = &*(lparam.0 as *const CREATESTRUCTW);
+        SetWindowLongPtrW(hwnd, GWLP_USERDATA, create_struct.lpCreateParams as isize);
+    }
+
+    if let Some(inner) = inner_for(hwnd) {
+        if let Some(result) = (*inner).handle_message(hwnd, msg, wparam, lparam) {
+            return result;
+        }
+    }
+
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
@@ -0,0 +1,2366 @@
+//! Native Win32 window creation and control.
+
+#[cfg(feature = "accent_policy")]
+mod accent;
+mod class;
+mod drag;
+mod inner;
+mod style;
+mod subclass;
+
+use std::cell::Ref;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+
+use windows::core::PCWSTR;
+#[cfg(feature = "app_user_model_id")]
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{BOOL, COLORREF, HWND, LPARAM, POINT, RECT, SIZE, WPARAM};
+use windows::Win32::Graphics::Dwm::{
+    DwmEnableBlurBehindWindow, DwmFlush, DwmGetCompositionTimingInfo, DwmGetWindowAttribute,
+    DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR, DWMWA_CLOAK, DWMWA_CLOAKED,
+    DWMWA_TEXT_COLOR, DWMWINDOWATTRIBUTE, DWM_BB_ENABLE, DWM_BLURBEHIND, DWM_TIMING_INFO,
+};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, ClientToScreen, CreateBitmap, CreateCompatibleDC, CreateEllipticRgn,
+    CreatePolygonRgn, CreateRectRgn, CreateRoundRectRgn, DeleteDC, DeleteObject, EndPaint,
+    GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, SelectObject, SetWindowRgn, AC_SRC_ALPHA,
+    AC_SRC_OVER, BLENDFUNCTION, HDC, HRGN, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+    MONITOR_DEFAULTTOPRIMARY, PAINTSTRUCT, WINDING,
+};
+#[cfg(feature = "app_user_model_id")]
+use windows::Win32::Storage::EnhancedStorage::PKEY_AppUserModel_ID;
+#[cfg(feature = "app_user_model_id")]
+use windows::Win32::System::Com::CoTaskMemAlloc;
+#[cfg(feature = "app_user_model_id")]
+use windows::Win32::System::Com::StructuredStorage::{PropVariantClear, PROPVARIANT};
+use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_INPROC_SERVER};
+use windows::Win32::System::Ole::{
+    DoDragDrop, OleInitialize, OleUninitialize, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE,
+};
+use windows::Win32::System::Shutdown::{ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy};
+#[cfg(feature = "app_user_model_id")]
+use windows::Win32::System::Variant::VT_LPWSTR;
+use windows::Win32::UI::HiDpi::{AdjustWindowRectExForDpi, GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use windows::Win32::UI::Input::KeyboardAndMouse::{DragDetect, SetFocus};
+#[cfg(feature = "app_user_model_id")]
+use windows::Win32::UI::Shell::PropertiesSystem::{IPropertyStore, SHGetPropertyStoreForWindow};
+use windows::Win32::UI::Shell::{
+    ITaskbarList3, TaskbarList, THBF_ENABLED, THB_FLAGS, THB_ICON, THB_TOOLTIP, THUMBBUTTON,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    AllowSetForegroundWindow, CreateIconIndirect, CreateWindowExW, DestroyCursor, DestroyWindow,
+    EnableMenuItem, FlashWindowEx, GetClientRect, GetSystemMenu, GetWindowLongPtrW,
+    GetWindowPlacement, GetWindowRect, IsWindowVisible, LoadCursorW, PostMessageW,
+    SetForegroundWindow, SetLayeredWindowAttributes, SetWindowLongPtrW, SetWindowPos, ShowCursor,
+    ShowWindow, TrackPopupMenuEx, UpdateLayeredWindow, ASFW_ANY, CW_USEDEFAULT, FLASHWINFO,
+    FLASHWINFO_FLAGS, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, GWLP_USERDATA, GWLP_WNDPROC,
+    GWL_EXSTYLE, GWL_STYLE, HCURSOR, HICON, HWND_NOTOPMOST, HWND_TOPMOST, ICONINFO, IDC_ARROW,
+    IDC_CROSS, IDC_HAND, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE,
+    IDC_SIZEWE, IDC_WAIT, LWA_ALPHA, MF_BYCOMMAND, MF_ENABLED, MF_GRAYED, SC_CLOSE,
+    SHOW_WINDOW_CMD, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER,
+    SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SW_SHOW, SW_SHOWMAXIMIZED, SW_SHOWMINIMIZED,
+    SW_SHOWMINNOACTIVE, SW_SHOWNA, TPM_LEFTALIGN, TPM_RETURNCMD, TPM_RIGHTBUTTON, ULW_ALPHA,
+    WINDOWPLACEMENT, WINDOW_EX_STYLE, WINDOW_STYLE, WS_CHILD, WS_EX_LAYERED, WS_EX_NOACTIVATE,
+    WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_MAXIMIZEBOX, WS_MINIMIZEBOX,
+    WS_OVERLAPPEDWINDOW, WS_THICKFRAME,
+};
+
+#[cfg(feature = "device_notifications")]
+use crate::device::{DeviceClass, DeviceEvent};
+use crate::dib::rgba_to_bgra_dib;
+use crate::dpi::Dpi;
+use crate::error::{Error, Result};
+use crate::event_loop::Event;
+use crate::geometry::{Point2D, Rect2D, Size2D};
+use crate::gesture::Gesture;
+use crate::handler::WindowHandler;
+use crate::keyboard::{KeyCode, Keyboard};
+use crate::media::MediaCommand;
+use crate::menu::{Menu, MenuId};
+use crate::mouse::{Mouse, MouseClick, WheelDelta};
+use crate::power::PowerEvent;
+use crate::shell::Icon;
+use crate::theme::{Backdrop, Color, CornerPreference, Theme};
+use crate::touch::TouchContact;
+use crate::util::encode_wide;
+
+#[cfg(feature = "accent_policy")]
+pub use self::accent::AccentPolicy;
+use self::drag::{from_dropeffect, DataObject, DropSource};
+use self::inner::{register_window, wnd_proc, WindowInner, WM_USER_EVENT};
+pub use self::style::{WindowExStyle, WindowStyle};
+pub use self::subclass::Subclass;
+
+/// A native Win32 top-level window.
+///
+/// Dropping a `Window` destroys the underlying `HWND`.
+pub struct Window {
+    hwnd: HWND,
+    inner: Box<WindowInner>,
+}
+
+impl Window {
+    /// Starts building a new window with the given title.
+    pub fn builder(title: impl Into<String>) -> Builder {
+        Builder::new(title)
+    }
+
+    /// Adopts a pre-existing `hwnd` (e.g. handed over by a host application
+    /// or game launcher) as a skylight `Window`, wiring up its window
+    /// procedure and `GWLP_USERDATA` pointer exactly as [`Builder::build`]
+    /// does for windows it creates itself.
+    ///
+    /// Unlike [`Subclass::attach`], this fully replaces `hwnd`'s existing
+    /// window procedure rather than observing messages alongside it, so
+    /// use this only when skylight should take over the window's message
+    /// handling entirely; dropping the returned `Window` destroys `hwnd`,
+    /// just as it would for a window skylight created itself.
+    pub fn adopt(hwnd: HWND) -> Result<Self> {
+        let inner = Box::new(WindowInner::new(
+            Theme::default(),
+            Size2D::default(),
+            None,
+            None,
+            None,
+            true,
+            None,
+            false,
+        ));
+        let inner_ptr = Box::into_raw(inner);
+
+        // SAFETY: `hwnd` is a valid, live window handed over by the
+        // caller. Setting `GWLP_USERDATA` before swapping `GWLP_WNDPROC`
+        // ensures `wnd_proc` can already recover `inner_ptr` for the very
+        // first message it receives.
+        unsafe {
+            SetWindowLongPtrW(hwnd, GWLP_USERDATA, inner_ptr as isize);
+            SetWindowLongPtrW(hwnd, GWLP_WNDPROC, wnd_proc as *const () as isize);
+        }
+
+        // SAFETY: `inner_ptr` was stashed in `GWLP_USERDATA` above and is
+        // reclaimed here exactly once.
+        let inner = unsafe { Box::from_raw(inner_ptr) };
+        register_window();
+
+        let window = Window { hwnd, inner };
+        window.inner.dpi.set(Dpi::detect(hwnd));
+
+        Ok(window)
+    }
+
+    /// The raw window handle.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd
+    }
+
+    /// The window's current DPI: detected when the window was created and
+    /// updated on each `WM_DPICHANGED`.
+    pub fn dpi(&self) -> Dpi {
+        self.inner.dpi.get()
+    }
+
+    /// The client area's current size, in pixels, queried live via
+    /// `GetClientRect` rather than the value last reported by `WM_SIZE`.
+    pub fn inner_size(&self) -> Result<Size2D<i32>> {
+        let mut rect = RECT::default();
+        // SAFETY: `self.hwnd` is a valid, live window and `rect` is a
+        // valid out-parameter for the duration of the call.
+        unsafe { GetClientRect(self.hwnd, &mut rect) }.map_err(Error::from)?;
+        Ok(Size2D::new(rect.right - rect.left, rect.bottom - rect.top))
+    }
+
+    /// The window's current overall size, in pixels, including its
+    /// non-client area (title bar and borders).
+    pub fn outer_size(&self) -> Result<Size2D<i32>> {
+        let rect = self.outer_rect()?;
+        Ok(Size2D::new(rect.right - rect.left, rect.bottom - rect.top))
+    }
+
+    /// Returns `true` if the window has received a close request (e.g. the
+    /// user clicked the title-bar close button) that the application has
+    /// not yet acted on.
+    pub fn is_requesting_close(&self) -> bool {
+        self.inner.requesting_close.get()
+    }
+
+    /// Returns `true` if the OS has requested that the session end
+    /// (shutdown, restart, or log off) and the window did not veto it via
+    /// [`WindowHandler::on_shutdown_requested`].
+    pub fn is_requesting_shutdown(&self) -> bool {
+        self.inner.requesting_shutdown.get()
+    }
+
+    /// Temporarily blocks the system from ending the session while a
+    /// shutdown is pending, via `ShutdownBlockReasonCreate`. `reason` is
+    /// shown to the user in the shutdown UI's list of blocking
+    /// applications, e.g. "Saving your work...". Windows only waits a
+    /// short, OS-defined time regardless, so this buys a brief grace
+    /// period rather than an indefinite one; call
+    /// [`Window::unblock_shutdown`] as soon as the work is done.
+    pub fn block_shutdown(&self, reason: &str) -> Result<()> {
+        let reason = encode_wide(reason);
+        // SAFETY: `self.hwnd` is a valid, live window; `reason` stays alive
+        // for the duration of the call.
+        unsafe { ShutdownBlockReasonCreate(self.hwnd, PCWSTR(reason.as_ptr())) }
+            .map_err(Error::from)
+    }
+
+    /// Lifts a block installed by [`Window::block_shutdown`].
+    pub fn unblock_shutdown(&self) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe { ShutdownBlockReasonDestroy(self.hwnd) }.map_err(Error::from)
+    }
+
+    /// Returns `true` if the window has an outstanding `WM_PAINT` request.
+    pub fn is_requesting_paint(&self) -> bool {
+        self.inner.requesting_paint.get()
+    }
+
+    /// Removes and returns the invalid region from the most recent
+    /// `WM_PAINT`, in client coordinates, letting renderers redraw only
+    /// the affected area instead of the whole client rect.
+    pub fn take_paint_request(&self) -> Option<Rect2D<i32>> {
+        self.inner.take_paint_request()
+    }
+
+    /// Returns `true` if the window currently has keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.inner.is_focused()
+    }
+
+    /// Begins painting in response to [`Window::is_requesting_paint`],
+    /// wrapping `BeginPaint`/`EndPaint`. Dropping the returned guard calls
+    /// `EndPaint`, which validates the update region; apps must call this
+    /// (or otherwise validate) on every paint request, or Windows will
+    /// keep re-posting `WM_PAINT`.
+    pub fn begin_paint(&self) -> PaintGuard<'_> {
+        let mut paint = PAINTSTRUCT::default();
+        // SAFETY: `self.hwnd` is a valid, live window and `paint` is a
+        // valid out-parameter for the duration of the call.
+        let hdc = unsafe { BeginPaint(self.hwnd, &mut paint) };
+        PaintGuard {
+            hwnd: self.hwnd,
+            hdc,
+            paint,
+            _window: std::marker::PhantomData,
+        }
+    }
+
+    /// The HDC to paint into for the current `WM_PRINTCLIENT` request, if
+    /// the handler/poller is being invoked from inside one; `None`
+    /// otherwise. `WM_PRINTCLIENT` is sent instead of `WM_PAINT` when the
+    /// OS wants the client area's content without an actual repaint, e.g.
+    /// for DWM thumbnails, Alt-Tab previews, and `AnimateWindow`; its HDC
+    /// is supplied directly rather than via `BeginPaint`, so
+    /// [`Window::begin_paint`] doesn't apply here.
+    pub fn print_client_target(&self) -> Option<HDC> {
+        self.inner.print_client_target()
+    }
+
+    /// Grants read access to keyboard state for this window.
+    pub fn keyboard(&self) -> Ref<'_, Keyboard> {
+        self.inner.keyboard.borrow()
+    }
+
+    /// Grants read access to mouse state for this window.
+    pub fn mouse(&self) -> Ref<'_, Mouse> {
+        self.inner.mouse.borrow()
+    }
+
+    /// Registers this window to receive raw `WM_INPUT` mouse motion,
+    /// useful for unaccelerated, high-precision deltas (e.g. FPS-style
+    /// camera controls) that system cursor acceleration would otherwise
+    /// distort.
+    pub fn enable_raw_input(&self) -> Result<()> {
+        self.inner.enable_raw_input(self.hwnd)
+    }
+
+    /// Removes and returns all raw mouse deltas accumulated since the last
+    /// call. Always empty unless [`Window::enable_raw_input`] has been
+    /// called.
+    pub fn drain_raw_mouse_deltas(&self) -> Vec<(i32, i32)> {
+        self.inner.drain_raw_mouse_deltas()
+    }
+
+    /// Registers `T` as this window's user event type and returns a
+    /// [`UserEventSender`] that can post instances of it from any thread,
+    /// to be consumed via [`Window::drain_user_events`]. Only one user
+    /// event type is tracked at a time; registering again replaces it.
+    pub fn register_user_events<T: Send + 'static>(&self) -> UserEventSender<T> {
+        self.inner
+            .set_user_event_reconstructor(reconstruct_user_event::<T>);
+        UserEventSender {
+            hwnd: self.hwnd,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Removes and returns all `T` events posted via a [`UserEventSender`]
+    /// since the last call. Always empty unless [`Window::register_user_events`]
+    /// has been called for `T`.
+    pub fn drain_user_events<T: Send + 'static>(&self) -> Vec<T> {
+        self.inner
+            .drain_user_events()
+            .into_iter()
+            .filter_map(|event| event.downcast::<T>().ok())
+            .map(|event| *event)
+            .collect()
+    }
+
+    /// Returns a channel [`Receiver`] fed every [`Event`] directly from the
+    /// window procedure, for apps that prefer consuming events from another
+    /// thread over polling [`Window::is_requesting_close`] and friends (or
+    /// driving an [`EventLoop`](crate::event_loop::EventLoop)). Only one
+    /// receiver is active at a time; calling this again replaces it.
+    pub fn events(&self) -> Receiver<Event> {
+        let (sender, receiver) = mpsc::channel();
+        self.inner.set_event_sender(sender);
+        receiver
+    }
+
+    /// A counter bumped on every `WM_PAINT`, used by [`crate::event_loop`]
+    /// to detect repaints even though [`Window::is_requesting_paint`] never
+    /// resets on its own.
+    pub(crate) fn paint_generation(&self) -> u64 {
+        self.inner.paint_generation.get()
+    }
+
+    /// Removes and returns the most recent size reported by `WM_SIZE`, if
+    /// the window has been resized since the last call.
+    pub(crate) fn take_resize(&self) -> Option<Size2D<i32>> {
+        self.inner.take_resize()
+    }
+
+    /// Removes and returns all key transitions accumulated since the last
+    /// call.
+    pub(crate) fn drain_key_transitions(&self) -> Vec<(KeyCode, bool)> {
+        self.inner.drain_key_transitions()
+    }
+
+    /// Sets the smallest size the user may resize the window to, or `None`
+    /// to remove the constraint. The limit applies to the outer window
+    /// rectangle, frame included, not to the client area.
+    pub fn set_min_size(&self, size: impl Into<Option<Size2D<i32>>>) {
+        self.inner.set_min_size(size.into());
+    }
+
+    /// Sets the largest size the user may resize the window to, or `None`
+    /// to remove the constraint. The limit applies to the outer window
+    /// rectangle, frame included, not to the client area.
+    pub fn set_max_size(&self, size: impl Into<Option<Size2D<i32>>>) {
+        self.inner.set_max_size(size.into());
+    }
+
+    /// Maximizes the window.
+    pub fn maximize(&self) {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = ShowWindow(self.hwnd, SW_MAXIMIZE);
+        }
+    }
+
+    /// Minimizes the window.
+    pub fn minimize(&self) {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = ShowWindow(self.hwnd, SW_MINIMIZE);
+        }
+    }
+
+    /// Restores the window from a minimized or maximized state.
+    pub fn restore(&self) {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = ShowWindow(self.hwnd, SW_RESTORE);
+        }
+    }
+
+    /// Shows the window without activating it, e.g. revealing a window
+    /// created with [`crate::window::InitialState::Hidden`] once its
+    /// content is ready.
+    pub fn show(&self) {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = ShowWindow(self.hwnd, SW_SHOWNA);
+        }
+    }
+
+    /// Hides the window, e.g. for a tray-minimized app. The window keeps
+    /// its HWND and state, so [`Window::show`] reveals it exactly as it
+    /// was.
+    pub fn hide(&self) {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = ShowWindow(self.hwnd, SW_HIDE);
+        }
+    }
+
+    /// Returns `true` if the window is currently shown, via
+    /// `IsWindowVisible`. A minimized window is still considered visible;
+    /// only [`Window::hide`] (or an [`InitialState::Hidden`] creation)
+    /// makes it `false`.
+    pub fn is_visible(&self) -> bool {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe { IsWindowVisible(self.hwnd) }.as_bool()
+    }
+
+    /// Brings the window to the foreground and gives it keyboard focus, via
+    /// `SetForegroundWindow`/`SetFocus`. Useful e.g. when a second instance
+    /// forwards its arguments to this one, which should then present
+    /// itself instead of quietly exiting.
+    ///
+    /// Windows only grants `SetForegroundWindow` to whichever process most
+    /// recently received user input, so this first calls
+    /// `AllowSetForegroundWindow(ASFW_ANY)` to lift that restriction for
+    /// this call. Even so, the OS may still refuse and merely flash the
+    /// taskbar button instead — nothing can force focus away from
+    /// whatever the user is currently interacting with.
+    pub fn focus(&self) {
+        // SAFETY: `ASFW_ANY` is a sentinel accepted in place of a process ID.
+        unsafe {
+            let _ = AllowSetForegroundWindow(ASFW_ANY);
+        }
+        // SAFETY: `self.hwnd` is a valid, live window.
+        unsafe {
+            let _ = SetForegroundWindow(self.hwnd);
+            let _ = SetFocus(Some(self.hwnd));
+        }
+    }
+
+    /// The window's current minimize/maximize/restore state.
+    pub fn state(&self) -> WindowState {
+        let mut placement = WINDOWPLACEMENT {
+            length: std::mem::size_of::<WINDOWPLACEMENT>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `placement.length` is set correctly above, as required.
+        if unsafe { GetWindowPlacement(self.hwnd, &mut placement) }.is_err() {
+            return WindowState::Normal;
+        }
+
+        match placement.showCmd {
+            cmd if cmd == SW_SHOWMAXIMIZED.0 as u32 => WindowState::Maximized,
+            cmd if cmd == SW_SHOWMINIMIZED.0 as u32 => WindowState::Minimized,
+            _ => WindowState::Normal,
+        }
+    }
+
+    /// Removes and returns the most recent minimize/maximize/restore
+    /// transition reported by `WM_SIZE`, if any.
+    pub(crate) fn take_state_change(&self) -> Option<WindowState> {
+        self.inner.take_state_change()
+    }
+
+    /// Removes and returns the most recent `WM_ENTERSIZEMOVE`/
+    /// `WM_EXITSIZEMOVE` transition (`true` for enter, `false` for exit),
+    /// if any.
+    pub(crate) fn take_size_move_change(&self) -> Option<bool> {
+        self.inner.take_size_move_change()
+    }
+
+    /// Returns `true` if the window has received at least one
+    /// `WM_DPICHANGED`, e.g. after being dragged to a monitor with a
+    /// different scale factor.
+    pub fn is_requesting_dpi_change(&self) -> bool {
+        self.inner.is_requesting_dpi_change()
+    }
+
+    /// A counter bumped on every `WM_DPICHANGED`, used by
+    /// [`crate::event_loop`] to detect DPI changes even though
+    /// [`Window::is_requesting_dpi_change`] never resets on its own.
+    pub(crate) fn dpi_generation(&self) -> u64 {
+        self.inner.dpi_generation()
+    }
+
+    /// Returns `true` if the system theme or high-contrast setting has
+    /// changed since the window was created, independent of whether the
+    /// window itself uses [`Theme::FollowSystem`]. Apps that render their
+    /// own UI colors can use this to know when to re-render them.
+    pub fn is_requesting_theme_change(&self) -> bool {
+        self.inner.is_requesting_theme_change()
+    }
+
+    /// A counter bumped on every system theme/high-contrast change, used by
+    /// [`crate::event_loop`] to detect changes even though
+    /// [`Window::is_requesting_theme_change`] never resets on its own.
+    pub(crate) fn theme_change_generation(&self) -> u64 {
+        self.inner.theme_change_generation()
+    }
+
+    /// The top-left corner of the window, including its non-client area
+    /// (title bar and borders), in screen coordinates.
+    pub fn outer_position(&self) -> Result<Point2D<i32>> {
+        let rect = self.outer_rect()?;
+        Ok(Point2D::new(rect.left, rect.top))
+    }
+
+    /// Moves the window so its top-left corner is at `position`, in screen
+    /// coordinates, without changing its size.
+    pub fn set_position(&self, position: Point2D<i32>) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window; `SWP_NOSIZE` means
+        // the size arguments are ignored.
+        unsafe {
+            SetWindowPos(
+                self.hwnd,
+                None,
+                position.x,
+                position.y,
+                0,
+                0,
+                SWP_NOSIZE | SWP_NOZORDER,
+            )
+            .map_err(Error::from)
+        }
+    }
+
+    /// Resizes the window so its client area becomes `size`, in physical
+    /// pixels, without moving its top-left corner. Accounts for the
+    /// non-client area (title bar and borders) at the window's current DPI,
+    /// via `AdjustWindowRectExForDpi`, so apps can snap to preset client
+    /// sizes (e.g. 720p/1080p capture sizes) after creation.
+    pub fn set_client_size(&self, size: Size2D<i32>) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window and `GWL_STYLE`/
+        // `GWL_EXSTYLE` are supported indices for `GetWindowLongPtrW`.
+        let style = WINDOW_STYLE(unsafe { GetWindowLongPtrW(self.hwnd, GWL_STYLE) } as u32);
+        let ex_style = WINDOW_EX_STYLE(unsafe { GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE) } as u32);
+
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: size.width,
+            bottom: size.height,
+        };
+        // SAFETY: `rect` is a valid in/out parameter for the duration of
+        // the call.
+        unsafe {
+            AdjustWindowRectExForDpi(
+                &mut rect,
+                style,
+                false,
+                ex_style,
+                Dpi::detect(self.hwnd).value(),
+            )
+        }
+        .map_err(Error::from)?;
+
+        // SAFETY: `self.hwnd` is a valid, live window; `SWP_NOMOVE` means
+        // the position arguments are ignored.
+        unsafe {
+            SetWindowPos(
+                self.hwnd,
+                None,
+                0,
+                0,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                SWP_NOMOVE | SWP_NOZORDER,
+            )
+            .map_err(Error::from)
+        }
+    }
+
+    /// Controls whether the user can resize the window by dragging its
+    /// edges, via `WS_THICKFRAME`. See also
+    /// [`Builder::with_resizable`](Builder::with_resizable) for setting this
+    /// at creation time.
+    pub fn set_resizable(&self, resizable: bool) -> Result<()> {
+        self.set_style_bit(WS_THICKFRAME, resizable)
+    }
+
+    /// Controls whether the window's maximize button is present and
+    /// double-clicking its title bar maximizes it, via `WS_MAXIMIZEBOX`.
+    pub fn set_maximizable(&self, maximizable: bool) -> Result<()> {
+        self.set_style_bit(WS_MAXIMIZEBOX, maximizable)
+    }
+
+    /// Controls whether the window's minimize button is present, via
+    /// `WS_MINIMIZEBOX`.
+    pub fn set_minimizable(&self, minimizable: bool) -> Result<()> {
+        self.set_style_bit(WS_MINIMIZEBOX, minimizable)
+    }
+
+    /// Enables or greys out the window's close button and the "Close" item
+    /// in its system menu, via `EnableMenuItem(SC_CLOSE)`. Alt+F4 and the
+    /// title bar's close button both become no-ops while disabled;
+    /// programmatically closing the window (dropping it) is unaffected.
+    /// Useful for wizards and installers that must not be interrupted
+    /// mid-operation.
+    pub fn set_close_button_enabled(&self, enabled: bool) {
+        let flags = if enabled {
+            MF_BYCOMMAND | MF_ENABLED
+        } else {
+            MF_BYCOMMAND | MF_GRAYED
+        };
+        // SAFETY: `self.hwnd` is a valid, live window; `false` means the
+        // system menu is queried rather than reset to its default layout.
+        let menu = unsafe { GetSystemMenu(self.hwnd, false) };
+        // SAFETY: `menu` is the system menu just retrieved above, which
+        // stays valid for the lifetime of `self.hwnd`.
+        unsafe {
+            EnableMenuItem(menu, SC_CLOSE, flags);
+        }
+    }
+
+    /// Sets or clears `bit` in the window's `GWL_STYLE`, then asks the
+    /// frame to redraw itself to reflect the change, via
+    /// `SWP_FRAMECHANGED`.
+    fn set_style_bit(&self, bit: WINDOW_STYLE, set: bool) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window and `GWL_STYLE` is a
+        // supported index for `GetWindowLongPtrW`/`SetWindowLongPtrW`.
+        let style = unsafe { GetWindowLongPtrW(self.hwnd, GWL_STYLE) };
+        let style = if set {
+            style | bit.0 as isize
+        } else {
+            style & !(bit.0 as isize)
+        };
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWL_STYLE, style);
+        }
+
+        // SAFETY: `self.hwnd` is a valid, live window; `SWP_NOMOVE |
+        // SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE` means only the frame
+        // is refreshed, nothing else about the window changes.
+        unsafe {
+            SetWindowPos(
+                self.hwnd,
+                None,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED,
+            )
+            .map_err(Error::from)
+        }
+    }
+
+    /// Sets whether the window stays above all other non-topmost windows,
+    /// via `SetWindowPos(HWND_TOPMOST)`/`SetWindowPos(HWND_NOTOPMOST)`.
+    /// Useful for overlays, timers, and picture-in-picture style tools.
+    pub fn set_always_on_top(&self, always_on_top: bool) -> Result<()> {
+        let insert_after = if always_on_top {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        // SAFETY: `self.hwnd` is a valid, live window; `SWP_NOMOVE |
+        // SWP_NOSIZE` means the position/size arguments are ignored.
+        unsafe {
+            SetWindowPos(
+                self.hwnd,
+                Some(insert_after),
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE,
+            )
+            .map_err(Error::from)
+        }
+    }
+
+    /// Toggles whether the window passes mouse clicks through to whatever
+    /// is beneath it, via `WS_EX_TRANSPARENT | WS_EX_LAYERED`. Suited to
+    /// overlay windows — crosshairs, FPS counters, annotations — that
+    /// should stay visible but never receive input.
+    pub fn set_hit_test_transparent(&self, transparent: bool) -> Result<()> {
+        let flags = (WS_EX_LAYERED.0 | WS_EX_TRANSPARENT.0) as isize;
+
+        // SAFETY: `self.hwnd` is a valid, live window and `GWL_EXSTYLE` is
+        // a supported index for `GetWindowLongPtrW`/`SetWindowLongPtrW`.
+        let style = unsafe { GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE) };
+        let style = if transparent {
+            style | flags
+        } else {
+            style & !flags
+        };
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWL_EXSTYLE, style);
+        }
+
+        if transparent {
+            // SAFETY: `self.hwnd` was just given `WS_EX_LAYERED` above, as
+            // `SetLayeredWindowAttributes` requires; full opacity here
+            // only changes hit-testing, not appearance.
+            unsafe { SetLayeredWindowAttributes(self.hwnd, COLORREF(0), 255, LWA_ALPHA) }
+                .map_err(Error::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the window's overall opacity, via `WS_EX_LAYERED` and
+    /// `SetLayeredWindowAttributes`. `opacity` is clamped to `0.0`
+    /// (fully transparent) through `1.0` (fully opaque). Suited to
+    /// fade-able tool windows and notifications.
+    pub fn set_opacity(&self, opacity: f32) -> Result<()> {
+        let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        // SAFETY: `self.hwnd` is a valid, live window and `GWL_EXSTYLE` is
+        // a supported index for `GetWindowLongPtrW`/`SetWindowLongPtrW`.
+        let style = unsafe { GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE) };
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as isize);
+        }
+
+        // SAFETY: `self.hwnd` was just given `WS_EX_LAYERED` above, as
+        // `SetLayeredWindowAttributes` requires.
+        unsafe { SetLayeredWindowAttributes(self.hwnd, COLORREF(0), alpha, LWA_ALPHA) }
+            .map_err(Error::from)
+    }
+
+    /// Paints the window's entire client area from `width` x `height`
+    /// straight-alpha RGBA8 pixel data, row-major top-to-bottom, via
+    /// `UpdateLayeredWindow`. Each pixel's alpha is composited against
+    /// whatever is beneath the window, allowing non-rectangular shapes and
+    /// soft shadows; pixels the window manager should treat as fully
+    /// see-through should carry alpha `0`.
+    ///
+    /// Gives the window `WS_EX_LAYERED`, superseding any opacity set via
+    /// [`Window::set_opacity`] — the two shouldn't be used together.
+    ///
+    /// Panics if `pixels` is shorter than `width * height * 4` bytes.
+    pub fn set_alpha_bitmap(&self, width: i32, height: i32, pixels: &[u8]) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window and `GWL_EXSTYLE` is a
+        // supported index for `GetWindowLongPtrW`/`SetWindowLongPtrW`.
+        let style = unsafe { GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE) };
+        unsafe {
+            SetWindowLongPtrW(self.hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as isize);
+        }
+
+        let (bitmap, bits) = rgba_to_bgra_dib(width, height, pixels.len())?;
+
+        // SAFETY: `bits` was sized by `rgba_to_bgra_dib` above for exactly
+        // `width * height` 32bpp pixels.
+        let dst = unsafe {
+            std::slice::from_raw_parts_mut(bits, (width as usize) * (height as usize) * 4)
+        };
+        for (src, dst) in pixels.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+            // RGBA -> premultiplied BGRA, as `UpdateLayeredWindow` expects
+            // with `AC_SRC_ALPHA`.
+            let alpha = src[3] as u16;
+            dst[0] = ((src[2] as u16 * alpha) / 255) as u8;
+            dst[1] = ((src[1] as u16 * alpha) / 255) as u8;
+            dst[2] = ((src[0] as u16 * alpha) / 255) as u8;
+            dst[3] = src[3];
+        }
+
+        // SAFETY: `None` requests a device context compatible with the
+        // screen, which `bitmap` (a DIB section) can be selected into.
+        let dc = unsafe { CreateCompatibleDC(None) };
+        // SAFETY: `bitmap` was just created above and matches a device
+        // context selectable into `dc`; the previously selected object
+        // (the DC's default 1x1 monochrome bitmap) is discarded, which is
+        // safe since `dc` is never used for anything else.
+        let previous = unsafe { SelectObject(dc, bitmap.into()) };
+
+        let size = SIZE {
+            cx: width,
+            cy: height,
+        };
+        let source_origin = POINT::default();
+        let blend = BLENDFUNCTION {
+            BlendOp: AC_SRC_OVER as u8,
+            BlendFlags: 0,
+            SourceConstantAlpha: 255,
+            AlphaFormat: AC_SRC_ALPHA as u8,
+        };
+        // SAFETY: `dc` has `bitmap` selected into it, sized `width` x
+        // `height` to match `size`; `self.hwnd` is a valid, live window.
+        let result = unsafe {
+            UpdateLayeredWindow(
+                self.hwnd,
+                None,
+                None,
+                Some(&size),
+                Some(dc),
+                Some(&source_origin),
+                COLORREF(0),
+                Some(&blend),
+                ULW_ALPHA,
+            )
+        };
+
+        // SAFETY: `previous` was selected out of `dc` above, and both `dc`
+        // and `bitmap` are owned solely by this function from here on.
+        unsafe {
+            SelectObject(dc, previous);
+            let _ = DeleteDC(dc);
+            let _ = DeleteObject(bitmap.into());
+        }
+
+        result.map_err(Error::from)
+    }
+
+    /// Clips the window to a non-rectangular `region`, via `SetWindowRgn`,
+    /// for classic shaped-window effects. Passing `None` restores the
+    /// window's ordinary rectangular shape.
+    pub fn set_region(&self, region: Option<&Region>) -> Result<()> {
+        let region = region.map(region_to_hrgn).transpose()?;
+
+        // SAFETY: `self.hwnd` is a valid, live window. On success, `SetWindowRgn`
+        // takes ownership of `region`'s handle; on failure it remains
+        // ours to free.
+        let result = unsafe { SetWindowRgn(self.hwnd, region, true) };
+        if result == 0 {
+            if let Some(region) = region {
+                // SAFETY: `SetWindowRgn` failed, so ownership of `region`
+                // never transferred to the system.
+                unsafe {
+                    let _ = DeleteObject(region.into());
+                }
+            }
+            return Err(Error::from(windows::core::Error::from_win32()));
+        }
+        Ok(())
+    }
+
+    /// Centers the window on the monitor it currently occupies the most of.
+    pub fn center_on_monitor(&self) -> Result<()> {
+        let outer = self.outer_rect()?;
+        let width = outer.right - outer.left;
+        let height = outer.bottom - outer.top;
+
+        // SAFETY: `self.hwnd` is a valid, live window.
+        let monitor = unsafe { MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST) };
+
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `info.cbSize` is set correctly above, as required.
+        if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+            return Err(Error::from(windows::core::Error::from_win32()));
+        }
+
+        let work_area = info.rcWork;
+        let x = work_area.left + ((work_area.right - work_area.left) - width) / 2;
+        let y = work_area.top + ((work_area.bottom - work_area.top) - height) / 2;
+
+        self.set_position(Point2D::new(x, y))
+    }
+
+    /// The window's full bounding rectangle, including its non-client area,
+    /// in screen coordinates.
+    fn outer_rect(&self) -> Result<RECT> {
+        let mut rect = RECT::default();
+        // SAFETY: `rect` is a valid out-parameter for the duration of the
+        // call.
+        unsafe { GetWindowRect(self.hwnd, &mut rect) }.map_err(Error::from)?;
+        Ok(rect)
+    }
+
+    /// Sets the title bar's background color (Windows 11 only).
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored.
+    pub fn set_caption_color(&self, color: Color) {
+        self.set_dwm_color(DWMWA_CAPTION_COLOR, color);
+    }
+
+    /// Sets the window border's color (Windows 11 only).
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored.
+    pub fn set_border_color(&self, color: Color) {
+        self.set_dwm_color(DWMWA_BORDER_COLOR, color);
+    }
+
+    /// Sets the title bar's text color (Windows 11 only).
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored.
+    pub fn set_caption_text_color(&self, color: Color) {
+        self.set_dwm_color(DWMWA_TEXT_COLOR, color);
+    }
+
+    /// Cloaks or uncloaks the window, via `DWMWA_CLOAK`. A cloaked window
+    /// keeps its HWND alive and composable but is never drawn to the
+    /// screen, letting an app finish preparing its content — e.g. laying
+    /// out a DirectComposition surface — before revealing it, avoiding the
+    /// white flash of an ordinary `ShowWindow`.
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored.
+    pub fn set_cloaked(&self, cloaked: bool) {
+        let value = BOOL::from(cloaked);
+        // SAFETY: `self.hwnd` is a valid window handle and `value` matches
+        // the `BOOL`-sized attribute `DWMWA_CLOAK` expects.
+        let _ = unsafe {
+            DwmSetWindowAttribute(
+                self.hwnd,
+                DWMWA_CLOAK,
+                std::ptr::addr_of!(value).cast(),
+                std::mem::size_of::<BOOL>() as u32,
+            )
+        };
+    }
+
+    /// Whether the window is currently cloaked, via `DWMWA_CLOAKED`. This
+    /// reports `true` both for windows cloaked with [`Window::set_cloaked`]
+    /// and for windows the system cloaks on its own, e.g. inactive UWP
+    /// windows on another virtual desktop.
+    pub fn is_cloaked(&self) -> bool {
+        let mut value: u32 = 0;
+        // SAFETY: `self.hwnd` is a valid window handle and `value` matches
+        // the `DWORD`-sized attribute `DWMWA_CLOAKED` expects.
+        let result = unsafe {
+            DwmGetWindowAttribute(
+                self.hwnd,
+                DWMWA_CLOAKED,
+                std::ptr::addr_of_mut!(value).cast(),
+                std::mem::size_of::<u32>() as u32,
+            )
+        };
+        result.is_ok() && value != 0
+    }
+
+    /// Enables or disables a gaussian blur of whatever is behind the
+    /// window's entire client area, via `DwmEnableBlurBehindWindow`. Works
+    /// on all DWM-composited versions of Windows, unlike [`Backdrop`],
+    /// which is Windows 11 only.
+    pub fn set_blur_behind(&self, enabled: bool) -> Result<()> {
+        let info = DWM_BLURBEHIND {
+            dwFlags: DWM_BB_ENABLE,
+            fEnable: enabled.into(),
+            hRgnBlur: HRGN::default(),
+            fTransitionOnMaximized: false.into(),
+        };
+        // SAFETY: `self.hwnd` is a valid, live window and `info` is fully
+        // initialized, with `hRgnBlur` left null to blur the whole client
+        // area.
+        unsafe { DwmEnableBlurBehindWindow(self.hwnd, &info) }.map_err(Error::from)
+    }
+
+    /// Applies an accent-blur policy via the undocumented
+    /// `SetWindowCompositionAttribute`, for acrylic-like translucent
+    /// panels on Windows 10 builds that predate [`Backdrop::Acrylic`].
+    /// Gated behind the `accent_policy` feature, since Microsoft can
+    /// change or remove this API at any time without notice.
+    #[cfg(feature = "accent_policy")]
+    pub fn set_accent_policy(&self, policy: AccentPolicy) -> Result<()> {
+        let (accent_state, gradient_color) = match policy {
+            AccentPolicy::Disabled => (accent::ACCENT_DISABLED, 0),
+            AccentPolicy::BlurBehind => (accent::ACCENT_ENABLE_BLURBEHIND, 0),
+            AccentPolicy::AcrylicBlurBehind { tint } => {
+                (accent::ACCENT_ENABLE_ACRYLICBLURBEHIND, tint)
+            }
+        };
+        let policy = accent::AccentPolicyRaw {
+            accent_state,
+            accent_flags: 0,
+            gradient_color,
+            animation_id: 0,
+        };
+        let data = accent::WindowCompositionAttribData {
+            attribute: accent::WCA_ACCENT_POLICY,
+            data: std::ptr::addr_of!(policy),
+            size_of_data: std::mem::size_of::<accent::AccentPolicyRaw>() as u32,
+        };
+        // SAFETY: `self.hwnd` is a valid, live window; `data` describes
+        // `policy`, which outlives the call.
+        unsafe { accent::set_window_composition_attribute(self.hwnd, &data) }.map_err(Error::from)
+    }
+
+    /// Sets the window's corner rounding preference (Windows 11 only).
+    ///
+    /// This is best-effort: on builds of Windows that predate this
+    /// attribute the call simply fails and is ignored.
+    pub fn set_corner_preference(&self, preference: CornerPreference) {
+        preference.apply(self.hwnd);
+    }
+
+    /// Sets the cursor shown while the pointer is over the window's client
+    /// area.
+    pub fn set_cursor(&self, cursor: SystemCursor) -> Result<()> {
+        // SAFETY: `cursor.idc()` names a built-in cursor resource that
+        // always exists.
+        let cursor = unsafe { LoadCursorW(None, cursor.idc()) }.map_err(Error::from)?;
+        self.inner.set_cursor(cursor);
+        Ok(())
+    }
+
+    /// Sets the cursor shown while the pointer is over the window's client
+    /// area to a [`CustomCursor`] built from application-supplied image
+    /// data. The window takes ownership of `cursor`, keeping it alive for
+    /// as long as it remains the active cursor.
+    pub fn set_custom_cursor(&self, cursor: CustomCursor) {
+        self.inner.set_custom_cursor(cursor);
+    }
+
+    /// Sets the window's title bar, taskbar, and `Alt+Tab` icon. The window
+    /// takes ownership of `icon`, keeping it alive for as long as it
+    /// remains active.
+    pub fn set_icon(&self, icon: Icon) {
+        self.inner.set_icon(self.hwnd, icon);
+    }
+
+    /// Shows or hides the system cursor.
+    pub fn set_cursor_visible(&self, visible: bool) {
+        // SAFETY: `ShowCursor` is always safe to call.
+        unsafe {
+            ShowCursor(visible);
+        }
+    }
+
+    /// Confines the system cursor to the window, e.g. for first-person
+    /// camera controls. See [`CursorGrab`] for the available modes.
+    pub fn set_cursor_grab(&self, grab: CursorGrab) -> Result<()> {
+        self.inner.set_cursor_grab(self.hwnd, grab)
+    }
+
+    /// Captures or releases the mouse, so drag interactions keep receiving
+    /// button-up and move messages even once the cursor leaves the client
+    /// area. Capture can also be taken away involuntarily, e.g. by another
+    /// window calling `SetCapture`; see [`Window::has_mouse_capture`].
+    pub fn set_mouse_capture(&self, capture: bool) -> Result<()> {
+        self.inner.set_mouse_capture(self.hwnd, capture)
+    }
+
+    /// Returns `true` if this window currently holds the mouse capture.
+    pub fn has_mouse_capture(&self) -> bool {
+        self.inner.has_mouse_capture()
+    }
+
+    /// Blocks until the mouse either moves beyond the system's drag
+    /// threshold (`SM_CXDRAG`/`SM_CYDRAG`) or the mouse button is released,
+    /// starting from `start` in client coordinates. Returns `true` if the
+    /// mouse dragged, distinguishing a click from the start of a drag for
+    /// list and canvas UIs. Call from a button-down handler, before the
+    /// button-up message would otherwise arrive.
+    pub fn detect_drag(&self, start: Point2D<i32>) -> bool {
+        let mut point = POINT {
+            x: start.x,
+            y: start.y,
+        };
+        // SAFETY: `point` is a valid, fully-initialized `POINT` and
+        // `self.hwnd` is a valid, live window.
+        unsafe { ClientToScreen(self.hwnd, &mut point) };
+        // SAFETY: `point` is a valid, fully-initialized `POINT` in screen
+        // coordinates.
+        unsafe { DragDetect(self.hwnd, point) }.as_bool()
+    }
+
+    /// Registers the client-area regions, in client coordinates, that
+    /// should behave like the title bar for dragging and double-click
+    /// maximize. Only meaningful when the window was built with
+    /// [`Builder::with_decorations`]`(false)`, since a decorated window
+    /// already has a real caption.
+    pub fn set_drag_regions(&self, regions: &[Rect2D<i32>]) {
+        self.inner.set_drag_regions(regions.to_vec());
+    }
+
+    /// Registers the client-area regions, in client coordinates, that
+    /// should behave like the minimize/maximize/close caption buttons.
+    /// Only meaningful when the window was built with
+    /// [`Builder::with_decorations`]`(false)`.
+    pub fn set_caption_buttons(&self, buttons: &[(CaptionButton, Rect2D<i32>)]) {
+        self.inner.set_caption_buttons(buttons.to_vec());
+    }
+
+    /// Attaches `menu` as the window's menu bar, replacing any previously
+    /// attached menu. The window takes ownership of `menu`, keeping it
+    /// alive for as long as it remains attached.
+    pub fn set_menu_bar(&self, menu: Menu) -> Result<()> {
+        self.inner.set_menu_bar(self.hwnd, menu)
+    }
+
+    /// Removes and returns all menu selections accumulated since the last
+    /// call, resolved against `Id`'s [`MenuId::from_raw`]. Selections that
+    /// don't resolve to a value of `Id` are silently discarded.
+    pub fn drain_menu_events<Id: MenuId>(&self) -> Vec<Id> {
+        self.inner
+            .drain_menu_selections()
+            .into_iter()
+            .filter_map(Id::from_raw)
+            .collect()
+    }
+
+    /// Removes and returns all child control notifications (see
+    /// [`crate::controls`]) accumulated since the last call, as `(id,
+    /// notification code)` pairs, resolved against `Id`'s
+    /// [`MenuId::from_raw`]. Notifications whose id doesn't resolve to a
+    /// value of `Id` are silently discarded.
+    pub fn drain_control_events<Id: MenuId>(&self) -> Vec<(Id, u32)> {
+        self.inner
+            .drain_control_notifications()
+            .into_iter()
+            .filter_map(|(id, notification)| Some((Id::from_raw(id)?, notification)))
+            .collect()
+    }
+
+    /// Shows `menu` as a popup at `position` (client coordinates), blocking
+    /// until it is dismissed, and returns the selected item, or `None` if
+    /// the user dismissed it without choosing one.
+    ///
+    /// Unlike [`Window::set_menu_bar`], the caller does not need to observe
+    /// `WM_COMMAND` or otherwise contend with message-loop re-entrancy:
+    /// the selection is returned directly.
+    pub fn show_context_menu<Id: MenuId>(
+        &self,
+        menu: &Menu,
+        position: Point2D<i32>,
+    ) -> Result<Option<Id>> {
+        let mut point = POINT {
+            x: position.x,
+            y: position.y,
+        };
+        // SAFETY: `point` is a valid in/out parameter and `self.hwnd` is a
+        // valid, live window.
+        unsafe { ClientToScreen(self.hwnd, &mut point) };
+
+        // SAFETY: `menu.handle()` is a valid menu kept alive by the caller
+        // for the duration of this call, and `self.hwnd` is a valid, live
+        // window. `TPM_RETURNCMD` makes the call block until the menu is
+        // dismissed and return the selected item directly, rather than
+        // posting a `WM_COMMAND`.
+        let selected = unsafe {
+            TrackPopupMenuEx(
+                menu.handle(),
+                (TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_LEFTALIGN).0,
+                point.x,
+                point.y,
+                self.hwnd,
+                None,
+            )
+        };
+
+        Ok(Id::from_raw(selected.0 as u32))
+    }
+
+    /// Removes and returns all media/browser commands (play/pause, track
+    /// navigation, volume, browser back/forward) accumulated since the
+    /// last call, e.g. from a keyboard's dedicated media keys.
+    pub fn drain_media_commands(&self) -> Vec<MediaCommand> {
+        self.inner.drain_media_commands()
+    }
+
+    /// Removes and returns all power/battery notifications accumulated
+    /// since the last call, from `WM_POWERBROADCAST`, so apps can pause
+    /// background work on battery or save state before the system sleeps.
+    pub fn drain_power_events(&self) -> Vec<PowerEvent> {
+        self.inner.drain_power_events()
+    }
+
+    /// Removes and returns all device arrival/removal notifications
+    /// accumulated since the last call, from `WM_DEVICECHANGE`, for the
+    /// [`DeviceClass`]es subscribed to via
+    /// [`Builder::with_device_notifications`].
+    #[cfg(feature = "device_notifications")]
+    pub fn drain_device_events(&self) -> Vec<(DeviceClass, DeviceEvent)> {
+        self.inner.drain_device_events()
+    }
+
+    /// Removes and returns all multi-touch contacts accumulated since the
+    /// last call, from `WM_POINTER*` messages.
+    pub fn drain_touch_contacts(&self) -> Vec<TouchContact> {
+        self.inner.drain_touch_contacts()
+    }
+
+    /// Removes and returns all two-finger pinch/rotate/pan gestures
+    /// accumulated since the last call, derived from touch contacts.
+    pub fn drain_gestures(&self) -> Vec<Gesture> {
+        self.inner.drain_gestures()
+    }
+
+    /// Removes and returns all mouse-wheel deltas accumulated since the
+    /// last call, from `WM_MOUSEWHEEL` and `WM_MOUSEHWHEEL`.
+    pub fn drain_wheel_deltas(&self) -> Vec<WheelDelta> {
+        self.inner.drain_wheel_deltas()
+    }
+
+    /// Removes and returns all button clicks accumulated since the last
+    /// call, with Windows' standard double/triple-click semantics applied
+    /// via `GetDoubleClickTime` and `SM_CXDOUBLECLK`/`SM_CYDOUBLECLK`.
+    pub fn drain_clicks(&self) -> Vec<MouseClick> {
+        self.inner.drain_clicks()
+    }
+
+    /// Enables or disables text input: while disabled, [`Keyboard::drain_input`]
+    /// stops accumulating `WM_CHAR` text and the window's IME is detached,
+    /// so keys held down for gameplay can't trigger IME composition.
+    /// Re-enable before showing a text field such as a chat box. Enabled by
+    /// default.
+    pub fn set_text_input_enabled(&self, enabled: bool) {
+        self.inner.set_text_input_enabled(self.hwnd, enabled);
+    }
+
+    /// Blocks the calling thread until the next time the desktop compositor
+    /// presents a frame, so a render loop can pace itself to the display's
+    /// refresh rate without busy-waiting or tearing.
+    pub fn wait_for_vblank(&self) -> Result<()> {
+        // SAFETY: `DwmFlush` takes no arguments and is always safe to call.
+        unsafe { DwmFlush() }.map_err(Error::from)
+    }
+
+    /// The monitor's current refresh rate, in Hz, as reported by the
+    /// desktop compositor. Since this is a live query rather than a cached
+    /// value, each call reflects the monitor the window is on at that
+    /// moment. No event reports a refresh-rate change on its own:
+    /// [`Event::DpiChanged`](crate::event_loop::Event::DpiChanged) fires
+    /// only when the window's DPI changes.
+    pub fn refresh_rate(&self) -> Result<f64> {
+        let mut info = DWM_TIMING_INFO {
+            cbSize: std::mem::size_of::<DWM_TIMING_INFO>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `info.cbSize` is set to the struct's true size and `info`
+        // is a valid out-parameter for the duration of the call.
+        unsafe { DwmGetCompositionTimingInfo(self.hwnd, &mut info) }.map_err(Error::from)?;
+
+        Ok(info.rateRefresh.uiNumerator as f64 / info.rateRefresh.uiDenominator as f64)
+    }
+
+    /// Starts an OLE drag-and-drop operation carrying `data`, blocking the
+    /// calling thread until the user drops it or cancels with Escape.
+    /// Returns the effect the drop target chose to perform.
+    pub fn start_drag(&self, data: DragData) -> Result<DropEffect> {
+        let allowed = DROPEFFECT_COPY | DROPEFFECT_MOVE | DROPEFFECT_LINK;
+        let mut effect = Default::default();
+
+        // SAFETY: `OleInitialize` is refcounted per-thread; it is always
+        // safe to call and is matched by the `OleUninitialize` call below.
+        unsafe { OleInitialize(None) }.map_err(Error::from)?;
+
+        // SAFETY: `&mut effect` is a valid out-parameter for the duration
+        // of the call, which blocks until the drag completes or is
+        // cancelled.
+        let result =
+            unsafe { DoDragDrop(DataObject::new(data), DropSource, allowed, &mut effect).ok() };
+
+        // SAFETY: matches the `OleInitialize` call above.
+        unsafe {
+            OleUninitialize();
+        }
+
+        result.map_err(Error::from)?;
+        Ok(from_dropeffect(effect))
+    }
+
+    /// Sets the icon overlaid on the window's taskbar button (e.g. for an
+    /// unread-message badge), or clears it if `icon` is `None`.
+    pub fn set_overlay_icon(&self, icon: Option<&Icon>) -> Result<()> {
+        let hicon = icon.map_or(HICON(std::ptr::null_mut()), Icon::handle);
+
+        // SAFETY: `OleInitialize` is refcounted per-thread; it is always
+        // safe to call and is matched by the `OleUninitialize` call below.
+        unsafe { OleInitialize(None) }.map_err(Error::from)?;
+
+        // SAFETY: `TaskbarList` identifies the in-process COM class
+        // implementing `ITaskbarList3`; `self.hwnd` is a valid window and
+        // `hicon`, if non-null, stays alive for the duration of this call.
+        let result: windows::core::Result<()> =
+            unsafe { CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER) }.and_then(
+                |taskbar: ITaskbarList3| unsafe {
+                    taskbar.SetOverlayIcon(self.hwnd, hicon, PCWSTR::null())
+                },
+            );
+
+        // SAFETY: matches the `OleInitialize` call above.
+        unsafe {
+            OleUninitialize();
+        }
+
+        result.map_err(Error::from)
+    }
+
+    /// Draws the user's attention to the window via [`Window::flash`], with
+    /// a default flash behavior chosen for `urgency`:
+    /// [`AttentionType::Informational`] flashes once,
+    /// [`AttentionType::Critical`] flashes until the window comes to the
+    /// foreground, at which point Windows stops the flash on its own.
+    pub fn request_attention(&self, urgency: AttentionType) {
+        self.flash(match urgency {
+            AttentionType::Informational => FlashMode::Once,
+            AttentionType::Critical => FlashMode::UntilForeground,
+        });
+    }
+
+    /// Flashes the window's caption and taskbar button to draw the user's
+    /// attention, per `mode`.
+    pub fn flash(&self, mode: FlashMode) {
+        let mut info = FLASHWINFO {
+            cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+            hwnd: self.hwnd,
+            dwFlags: mode.flags(),
+            uCount: if mode == FlashMode::Once { 1 } else { 0 },
+            dwTimeout: 0,
+        };
+
+        // SAFETY: `info` is fully initialized and describes `self.hwnd`,
+        // which outlives this call.
+        unsafe {
+            FlashWindowEx(&mut info);
+        }
+    }
+
+    /// Registers up to seven buttons on the window's taskbar thumbnail
+    /// preview via `ITaskbarList3::ThumbBarAddButtons`. Clicking a button
+    /// reports a `WM_COMMAND`-style notification through
+    /// [`Window::drain_control_events`], keyed by the same `Id` given here.
+    ///
+    /// May only be called once per window; Win32 rejects later calls, so
+    /// register every button up front rather than calling this repeatedly.
+    pub fn set_thumbnail_toolbar<Id: MenuId>(&self, buttons: &[(Id, &Icon, &str)]) -> Result<()> {
+        let tooltips: Vec<Vec<u16>> = buttons.iter().map(|(_, _, tip)| encode_wide(tip)).collect();
+
+        let thumb_buttons: Vec<THUMBBUTTON> = buttons
+            .iter()
+            .zip(&tooltips)
+            .map(|((id, icon, _), tip)| {
+                let mut button = THUMBBUTTON {
+                    dwMask: THB_ICON | THB_TOOLTIP | THB_FLAGS,
+                    iId: id.into_raw(),
+                    hIcon: icon.handle(),
+                    dwFlags: THBF_ENABLED,
+                    ..Default::default()
+                };
+                let len = tip.len().min(button.szTip.len() - 1);
+                button.szTip[..len].copy_from_slice(&tip[..len]);
+                button.szTip[len] = 0;
+                button
+            })
+            .collect();
+
+        // SAFETY: `OleInitialize` is refcounted per-thread; it is always
+        // safe to call and is matched by the `OleUninitialize` call below.
+        unsafe { OleInitialize(None) }.map_err(Error::from)?;
+
+        // SAFETY: `TaskbarList` identifies the in-process COM class
+        // implementing `ITaskbarList3`; `self.hwnd` is a valid window and
+        // every `hIcon` in `thumb_buttons` stays alive for the duration of
+        // this call.
+        let result: windows::core::Result<()> =
+            unsafe { CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER) }.and_then(
+                |taskbar: ITaskbarList3| unsafe {
+                    taskbar.ThumbBarAddButtons(self.hwnd, &thumb_buttons)
+                },
+            );
+
+        // SAFETY: matches the `OleInitialize` call above.
+        unsafe {
+            OleUninitialize();
+        }
+
+        result.map_err(Error::from)
+    }
+
+    /// Sets this window's AppUserModelID, via `SHGetPropertyStoreForWindow`
+    /// and `IPropertyStore::SetValue`, overriding the process-wide value set
+    /// by [`crate::proc::set_app_user_model_id`] for just this window.
+    #[cfg(feature = "app_user_model_id")]
+    pub fn set_app_user_model_id(&self, id: &str) -> Result<()> {
+        // SAFETY: `self.hwnd` is a valid, live window.
+        let store: IPropertyStore =
+            unsafe { SHGetPropertyStoreForWindow(self.hwnd) }.map_err(Error::from)?;
+
+        let mut value = property_variant_from_str(id)?;
+
+        // SAFETY: `store` and `&value` are both valid for the duration of
+        // these calls; `value` is cleared below regardless of the outcome.
+        let result = unsafe { store.SetValue(&PKEY_AppUserModel_ID, &value) }
+            .and_then(|()| unsafe { store.Commit() });
+
+        // SAFETY: `value` was built by `property_variant_from_str` above and
+        // has not been cleared yet.
+        unsafe {
+            let _ = PropVariantClear(&mut value);
+        }
+
+        result.map_err(Error::from)
+    }
+
+    fn set_dwm_color(&self, attribute: DWMWINDOWATTRIBUTE, color: Color) {
+        let colorref = color.to_colorref();
+        // SAFETY: `self.hwnd` is a valid window handle and `colorref`
+        // matches the `COLORREF`-sized attribute that each of these DWM
+        // attributes expects.
+        let _ = unsafe {
+            DwmSetWindowAttribute(
+                self.hwnd,
+                attribute,
+                std::ptr::addr_of!(colorref).cast(),
+                std::mem::size_of::<u32>() as u32,
+            )
+        };
+    }
+}
+
+/// A built-in cursor shape, settable per-window via [`Builder::with_cursor`]
+/// and [`Window::set_cursor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SystemCursor {
+    /// The standard arrow.
+    #[default]
+    Arrow,
+    /// A text-insertion caret, for editable text.
+    IBeam,
+    /// A pointing hand, for links and clickable controls.
+    Hand,
+    /// Crosshairs, for precise point selection.
+    Crosshair,
+    /// The "busy" spinner.
+    Wait,
+    /// The "not allowed" slashed circle.
+    No,
+    /// A four-way resize arrow.
+    SizeAll,
+    /// A vertical resize arrow, for top/bottom edges.
+    SizeNS,
+    /// A horizontal resize arrow, for left/right edges.
+    SizeWE,
+    /// A diagonal resize arrow, for the top-right/bottom-left corners.
+    SizeNESW,
+    /// A diagonal resize arrow, for the top-left/bottom-right corners.
+    SizeNWSE,
+}
+
+impl SystemCursor {
+    /// The built-in cursor resource identifier this shape maps to.
+    fn idc(self) -> PCWSTR {
+        match self {
+            Self::Arrow => IDC_ARROW,
+            Self::IBeam => IDC_IBEAM,
+            Self::Hand => IDC_HAND,
+            Self::Crosshair => IDC_CROSS,
+            Self::Wait => IDC_WAIT,
+            Self::No => IDC_NO,
+            Self::SizeAll => IDC_SIZEALL,
+            Self::SizeNS => IDC_SIZENS,
+            Self::SizeWE => IDC_SIZEWE,
+            Self::SizeNESW => IDC_SIZENESW,
+            Self::SizeNWSE => IDC_SIZENWSE,
+        }
+    }
+}
+
+/// Posts `T` events to a window registered via
+/// [`Window::register_user_events`], from any thread, including threads
+/// other than the one that created the window.
+pub struct UserEventSender<T> {
+    hwnd: HWND,
+    _marker: std::marker::PhantomData<fn(T)>,
+}
+
+// `HWND` is just an opaque handle; `PostMessageW` may be called from any
+// thread, which is the entire point of this type.
+unsafe impl<T> Send for UserEventSender<T> {}
+unsafe impl<T> Sync for UserEventSender<T> {}
+
+impl<T> Clone for UserEventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            hwnd: self.hwnd,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T: Send + 'static> UserEventSender<T> {
+    /// Posts `event` to the window's event stream, to be consumed via
+    /// [`Window::drain_user_events`].
+    pub fn post(&self, event: T) -> Result<()> {
+        let ptr = Box::into_raw(Box::new(event));
+        // Tags the message with the reconstructor for `T`, so whichever
+        // type is registered by the time it's pumped, `ptr` is always
+        // reconstructed as the `T` it was actually allocated as, never a
+        // different type a later `register_user_events` call swapped in.
+        let reconstruct = reconstruct_user_event::<T> as usize;
+        // SAFETY: `self.hwnd` is the `HWND` of a window that, as long as it
+        // hasn't been destroyed, reclaims `ptr` either when it processes
+        // the posted message or, if it's destroyed first, while draining
+        // its own message queue on `WM_DESTROY`.
+        let posted = unsafe {
+            PostMessageW(
+                Some(self.hwnd),
+                WM_USER_EVENT,
+                WPARAM(reconstruct),
+                LPARAM(ptr as isize),
+            )
+        };
+        if let Err(err) = posted {
+            // The message never made it into the queue, so nothing will
+            // ever reclaim `ptr`; reclaim it here instead.
+            drop(unsafe { Box::from_raw(ptr) });
+            return Err(Error::from(err));
+        }
+        Ok(())
+    }
+}
+
+/// Reconstructs a `T` previously boxed and posted by [`UserEventSender::post`]
+/// from its raw pointer, monomorphized per `T` so [`WindowInner`] can carry
+/// it without itself being generic. [`UserEventSender::post`] casts this
+/// same function to a `usize` and tags the posted message with it, so
+/// [`WindowInner`] always reconstructs a message's payload with the
+/// monomorphization matching the `T` it was actually boxed as, not whatever
+/// happens to be registered by the time the message is pumped.
+fn reconstruct_user_event<T: Send + 'static>(ptr: isize) -> Box<dyn std::any::Any + Send> {
+    // SAFETY: `ptr` came from `Box::into_raw(Box::<T>::new(..))` in
+    // `UserEventSender::post`, and is reconstructed at most once, here or
+    // in `WindowInner`'s `WM_DESTROY` cleanup.
+    unsafe { Box::from_raw(ptr as *mut T) }
+}
+
+/// Builds a GDI region matching `region`'s shape. The caller owns the
+/// returned handle until it passes it to `SetWindowRgn`.
+fn region_to_hrgn(region: &Region) -> Result<HRGN> {
+    let hrgn = match region {
+        Region::Rect(rect) => {
+            let rect = RECT::from(*rect);
+            // SAFETY: no preconditions beyond the arguments themselves.
+            unsafe { CreateRectRgn(rect.left, rect.top, rect.right, rect.bottom) }
+        }
+        Region::RoundedRect { rect, corner } => {
+            let rect = RECT::from(*rect);
+            // SAFETY: no preconditions beyond the arguments themselves.
+            unsafe {
+                CreateRoundRectRgn(
+                    rect.left,
+                    rect.top,
+                    rect.right,
+                    rect.bottom,
+                    corner.width,
+                    corner.height,
+                )
+            }
+        }
+        Region::Ellipse(rect) => {
+            let rect = RECT::from(*rect);
+            // SAFETY: no preconditions beyond the arguments themselves.
+            unsafe { CreateEllipticRgn(rect.left, rect.top, rect.right, rect.bottom) }
+        }
+        Region::Polygon(points) => {
+            let points: Vec<POINT> = points.iter().map(|point| POINT::from(*point)).collect();
+            // SAFETY: `points` is a live slice for the duration of the call.
+            unsafe { CreatePolygonRgn(&points, WINDING) }
+        }
+    };
+
+    if hrgn.is_invalid() {
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+    Ok(hrgn)
+}
+
+/// Builds a `VT_LPWSTR` [`PROPVARIANT`] holding a heap copy of `text`,
+/// allocated with `CoTaskMemAlloc` as `IPropertyStore::SetValue` expects.
+/// The returned value must be freed with `PropVariantClear`.
+#[cfg(feature = "app_user_model_id")]
+fn property_variant_from_str(text: &str) -> Result<PROPVARIANT> {
+    let encoded = encode_wide(text);
+    let size = std::mem::size_of_val(encoded.as_slice());
+
+    // SAFETY: `size` is nonzero and fits in memory, since `encoded` is
+    // already a live `Vec` of that same size.
+    let buffer = unsafe { CoTaskMemAlloc(size) };
+    if buffer.is_null() {
+        return Err(Error::from(windows::core::Error::from_win32()));
+    }
+
+    // SAFETY: `buffer` was just allocated above with room for exactly
+    // `encoded.len()` `u16`s.
+    unsafe {
+        std::ptr::copy_nonoverlapping(encoded.as_ptr(), buffer.cast::<u16>(), encoded.len());
+    }
+
+    let mut variant = PROPVARIANT::default();
+    // SAFETY: `variant.Anonymous.Anonymous` is zeroed, so overwriting its
+    // `vt` and `pwszVal` fields to describe a `VT_LPWSTR` string, whose
+    // buffer is the one just allocated above, leaves it in a state
+    // `PropVariantClear` knows how to free.
+    unsafe {
+        variant.Anonymous.Anonymous.vt = VT_LPWSTR;
+        variant.Anonymous.Anonymous.Anonymous.pwszVal = PWSTR(buffer.cast());
+    }
+    Ok(variant)
+}
+
+/// A custom cursor shape built from raw pixel data, for pointers beyond the
+/// built-in [`SystemCursor`] set. Set via [`Window::set_custom_cursor`].
+pub struct CustomCursor {
+    cursor: HCURSOR,
+}
+
+impl CustomCursor {
+    /// Builds a cursor from `width` x `height` straight-alpha RGBA8 pixel
+    /// data, row-major top-to-bottom, with the visual hotspot at `hotspot`
+    /// (in pixels, relative to the top-left corner).
+    ///
+    /// Panics if `pixels` is shorter than `width * height * 4` bytes.
+    pub fn from_rgba(
+        width: i32,
+        height: i32,
+        pixels: &[u8],
+        hotspot: Point2D<i32>,
+    ) -> Result<Self> {
+        let (color, bits) = rgba_to_bgra_dib(width, height, pixels.len())?;
+
+        // SAFETY: `bits` was sized by `rgba_to_bgra_dib` above for exactly
+        // `width * height` 32bpp pixels.
+        let dst = unsafe {
+            std::slice::from_raw_parts_mut(bits, (width as usize) * (height as usize) * 4)
+        };
+        for (src, dst) in pixels.chunks_exact(4).zip(dst.chunks_exact_mut(4)) {
+            // RGBA -> BGRA, matching the DIB's pixel layout.
+            dst[0] = src[2];
+            dst[1] = src[1];
+            dst[2] = src[0];
+            dst[3] = src[3];
+        }
+
+        // A cursor's AND mask is ignored once its color bitmap carries an
+        // alpha channel, so its content doesn't matter, only its
+        // dimensions.
+        // SAFETY: `None` requests a zero-initialized bitmap of this size.
+        let mask = unsafe { CreateBitmap(width, height, 1, 1, None) };
+
+        let icon_info = ICONINFO {
+            fIcon: false.into(),
+            xHotspot: hotspot.x as u32,
+            yHotspot: hotspot.y as u32,
+            hbmMask: mask,
+            hbmColor: color,
+        };
+        // SAFETY: `icon_info` is fully initialized and both bitmaps match
+        // its declared dimensions.
+        let icon = unsafe { CreateIconIndirect(&icon_info) };
+
+        // SAFETY: `CreateIconIndirect` duplicates both bitmaps into GDI
+        // objects it owns, so the originals must still be freed here.
+        unsafe {
+            let _ = DeleteObject(color.into());
+            let _ = DeleteObject(mask.into());
+        }
+
+        let icon = icon.map_err(Error::from)?;
+        Ok(Self {
+            cursor: HCURSOR(icon.0),
+        })
+    }
+
+    /// The underlying cursor handle.
+    pub(super) fn handle(&self) -> HCURSOR {
+        self.cursor
+    }
+}
+
+impl Drop for CustomCursor {
+    fn drop(&mut self) {
+        // SAFETY: `self.cursor` was created by `CreateIconIndirect` and is
+        // destroyed exactly once here.
+        unsafe {
+            let _ = DestroyCursor(self.cursor);
+        }
+    }
+}
+
+/// How a [`Window`] confines the system cursor, set via
+/// [`Window::set_cursor_grab`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorGrab {
+    /// The cursor moves freely, even outside the window.
+    #[default]
+    None,
+    /// The cursor is confined to the window's client area but still moves
+    /// normally within it.
+    Confined,
+    /// The cursor is held in place at the center of the client area;
+    /// combine with [`Window::enable_raw_input`] to read relative motion,
+    /// e.g. for first-person camera controls.
+    Locked,
+}
+
+/// A non-rectangular window clipping shape, set via [`Window::set_region`].
+/// Coordinates are in window coordinates, relative to the window's
+/// top-left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Region {
+    /// A plain rectangle.
+    Rect(Rect2D<i32>),
+    /// A rectangle with corners rounded by `corner`-sized ellipse arcs.
+    RoundedRect {
+        rect: Rect2D<i32>,
+        corner: Size2D<i32>,
+    },
+    /// An ellipse inscribed within `rect`.
+    Ellipse(Rect2D<i32>),
+    /// An arbitrary polygon, filled using the nonzero winding rule.
+    Polygon(Vec<Point2D<i32>>),
+}
+
+/// Content offered by [`Window::start_drag`] to the drop target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DragData {
+    /// Unicode text, offered as `CF_UNICODETEXT`.
+    Text(String),
+    /// A list of file paths, offered as `CF_HDROP`.
+    Paths(Vec<PathBuf>),
+}
+
+/// The effect a drop target chose to perform on data dropped from
+/// [`Window::start_drag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DropEffect {
+    /// The drop was cancelled or refused.
+    #[default]
+    None,
+    /// The data was copied.
+    Copy,
+    /// The data was moved.
+    Move,
+    /// A link (e.g. a shortcut) to the data was created.
+    Link,
+}
+
+/// A caption button registered via [`Window::set_caption_buttons`], so a
+/// custom-drawn title bar can still get the hover/press feedback and
+/// snap-layout flyout Windows gives its own caption buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptionButton {
+    /// The minimize button.
+    Minimize,
+    /// The maximize/restore button.
+    Maximize,
+    /// The close button.
+    Close,
+}
+
+/// How urgently [`Window::request_attention`] should draw the user's eye.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttentionType {
+    /// A one-off flash, for attention that isn't urgent.
+    Informational,
+    /// A flash that persists until the window comes to the foreground, for
+    /// attention that shouldn't be missed.
+    Critical,
+}
+
+/// How a window flashes via [`Window::flash`], to draw the user's
+/// attention without stealing focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlashMode {
+    /// Stops any flashing in progress, restoring the window to its
+    /// original state.
+    Stop,
+    /// Flashes the caption and taskbar button once.
+    Once,
+    /// Flashes the caption and taskbar button repeatedly until the window
+    /// comes to the foreground.
+    UntilForeground,
+}
+
+impl FlashMode {
+    fn flags(self) -> FLASHWINFO_FLAGS {
+        match self {
+            Self::Stop => FLASHW_STOP,
+            Self::Once => FLASHW_ALL,
+            Self::UntilForeground => FLASHW_TIMERNOFG,
+        }
+    }
+}
+
+/// An in-progress GDI paint, started by [`Window::begin_paint`]. `EndPaint`
+/// is called automatically on drop.
+pub struct PaintGuard<'a> {
+    hwnd: HWND,
+    hdc: HDC,
+    paint: PAINTSTRUCT,
+    _window: std::marker::PhantomData<&'a Window>,
+}
+
+impl PaintGuard<'_> {
+    /// The device context to draw into.
+    pub fn hdc(&self) -> HDC {
+        self.hdc
+    }
+
+    /// The invalid rectangle that triggered this paint, in client
+    /// coordinates.
+    pub fn invalid_rect(&self) -> Rect2D<i32> {
+        self.paint.rcPaint.into()
+    }
+}
+
+impl Drop for PaintGuard<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `self.paint` was filled in by the matching `BeginPaint`
+        // call in `Window::begin_paint`.
+        unsafe {
+            let _ = EndPaint(self.hwnd, &self.paint);
+        }
+    }
+}
+
+/// The minimize/maximize/restore state of a [`Window`], queried via
+/// [`Window::state`] or surfaced as a transition via
+/// [`crate::event_loop::Event::StateChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowState {
+    /// Neither minimized nor maximized.
+    Normal,
+    /// Minimized to the taskbar.
+    Minimized,
+    /// Maximized to fill the monitor's work area.
+    Maximized,
+}
+
+/// The state a [`Window`] is shown in immediately after creation, via
+/// [`Builder::with_initial_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InitialState {
+    /// Shown as an ordinary restored window.
+    #[default]
+    Normal,
+    /// Shown already maximized, e.g. when restoring a previous session.
+    Maximized,
+    /// Shown already minimized to the taskbar, without activating it.
+    Minimized,
+    /// Created without being shown at all, so the app can finish preparing
+    /// its content before revealing it with an explicit `ShowWindow` call.
+    Hidden,
+}
+
+impl InitialState {
+    fn show_cmd(self) -> SHOW_WINDOW_CMD {
+        match self {
+            Self::Normal => SW_SHOW,
+            Self::Maximized => SW_SHOWMAXIMIZED,
+            Self::Minimized => SW_SHOWMINNOACTIVE,
+            Self::Hidden => SW_HIDE,
+        }
+    }
+}
+
+/// A borrowed, read-only handle to a window, passed to
+/// [`WindowHandler`](crate::handler::WindowHandler) methods.
+///
+/// Mirrors the read-only parts of [`Window`]'s API, for use from inside a
+/// message handler, where only a reference to the per-window state (not the
+/// owning `Window`) is available.
+#[derive(Clone, Copy)]
+pub struct WindowHandle<'a> {
+    hwnd: HWND,
+    inner: &'a WindowInner,
+}
+
+impl<'a> WindowHandle<'a> {
+    pub(super) fn new(hwnd: HWND, inner: &'a WindowInner) -> Self {
+        Self { hwnd, inner }
+    }
+
+    /// The raw window handle.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd
+    }
+
+    /// The window's current DPI: detected when the window was created and
+    /// updated on each `WM_DPICHANGED`.
+    pub fn dpi(&self) -> Dpi {
+        self.inner.dpi.get()
+    }
+
+    /// Returns `true` if the window has received a close request that the
+    /// application has not yet acted on.
+    pub fn is_requesting_close(&self) -> bool {
+        self.inner.requesting_close.get()
+    }
+
+    /// Returns `true` if the OS has requested that the session end and the
+    /// window did not veto it. See
+    /// [`Window::is_requesting_shutdown`](crate::window::Window::is_requesting_shutdown)
+    /// for details.
+    pub fn is_requesting_shutdown(&self) -> bool {
+        self.inner.requesting_shutdown.get()
+    }
+
+    /// Returns `true` if the window has an outstanding `WM_PAINT` request.
+    pub fn is_requesting_paint(&self) -> bool {
+        self.inner.requesting_paint.get()
+    }
+
+    /// The HDC to paint into for the current `WM_PRINTCLIENT` request, if
+    /// this call is happening from inside one; `None` otherwise. See
+    /// [`Window::print_client_target`] for details.
+    pub fn print_client_target(&self) -> Option<HDC> {
+        self.inner.print_client_target()
+    }
+
+    /// Grants read access to keyboard state for this window.
+    pub fn keyboard(&self) -> Ref<'_, Keyboard> {
+        self.inner.keyboard.borrow()
+    }
+
+    /// Grants read access to mouse state for this window.
+    pub fn mouse(&self) -> Ref<'_, Mouse> {
+        self.inner.mouse.borrow()
+    }
+}
+
+impl Drop for Window {
+    fn drop(&mut self) {
+        // SAFETY: `self.hwnd` was created by this `Window` in `Builder::build`
+        // and has not yet been destroyed.
+        unsafe {
+            let _ = DestroyWindow(self.hwnd);
+        }
+    }
+}
+
+/// Builds a [`Window`] with optional configuration.
+pub struct Builder {
+    title: String,
+    size: Size2D<i32>,
+    position: Option<Point2D<i32>>,
+    theme: Theme,
+    handler: Option<Box<dyn WindowHandler>>,
+    min_size: Option<Size2D<i32>>,
+    max_size: Option<Size2D<i32>>,
+    caption_color: Option<Color>,
+    border_color: Option<Color>,
+    caption_text_color: Option<Color>,
+    backdrop: Backdrop,
+    corner_preference: CornerPreference,
+    decorations: bool,
+    background: Option<Color>,
+    live_resize: bool,
+    always_on_top: bool,
+    skip_taskbar: bool,
+    tool_window: bool,
+    opacity: Option<f32>,
+    composition_target: bool,
+    resizable: bool,
+    initial_state: InitialState,
+    style_overrides: WindowStyle,
+    ex_style_overrides: WindowExStyle,
+    cursor: SystemCursor,
+    icon: Option<Icon>,
+    parent: Option<HWND>,
+    owner: Option<HWND>,
+    #[cfg(feature = "device_notifications")]
+    device_notifications: Vec<DeviceClass>,
+}
+
+impl Builder {
+    fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            size: Size2D::new(1280, 720),
+            position: None,
+            theme: Theme::default(),
+            handler: None,
+            min_size: None,
+            max_size: None,
+            caption_color: None,
+            border_color: None,
+            caption_text_color: None,
+            backdrop: Backdrop::default(),
+            corner_preference: CornerPreference::default(),
+            decorations: true,
+            background: None,
+            live_resize: false,
+            always_on_top: false,
+            skip_taskbar: false,
+            tool_window: false,
+            opacity: None,
+            composition_target: false,
+            resizable: true,
+            initial_state: InitialState::default(),
+            style_overrides: WindowStyle::NONE,
+            ex_style_overrides: WindowExStyle::NONE,
+            cursor: SystemCursor::default(),
+            icon: None,
+            parent: None,
+            owner: None,
+            #[cfg(feature = "device_notifications")]
+            device_notifications: Vec::new(),
+        }
+    }
+
+    /// Sets the initial client-area size, in logical pixels.
+    pub fn with_size(mut self, size: Size2D<i32>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the initial window position, in screen coordinates. Defaults to
+    /// letting the OS choose.
+    pub fn with_position(mut self, position: Point2D<i32>) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Centers the window on whichever monitor will contain it — the one
+    /// under [`Builder::with_position`]'s point if set, otherwise the
+    /// primary monitor — instead of leaving its position to
+    /// `CW_USEDEFAULT`. Accounts for that monitor's DPI, since
+    /// [`Builder::with_size`] is specified in logical pixels.
+    ///
+    /// This is best-effort: if the target monitor can't be queried, the
+    /// position is left unset and the OS chooses as usual.
+    pub fn centered(mut self) -> Self {
+        let point = match self.position {
+            Some(position) => POINT::from(position),
+            None => POINT::default(),
+        };
+        // SAFETY: `point` is a plain value; `MonitorFromPoint` always
+        // returns a handle, falling back to the primary monitor when no
+        // monitor contains `point`.
+        let monitor = unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTOPRIMARY) };
+
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `monitor` is a valid monitor handle and `info.cbSize` is
+        // set correctly above, as `GetMonitorInfoW` requires.
+        if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+            return self;
+        }
+
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        // SAFETY: `monitor` is a valid monitor handle and `dpi_x`/`dpi_y`
+        // are valid out-parameters.
+        let dpi = unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) }
+            .map(|()| Dpi::from_raw(dpi_x))
+            .unwrap_or_default();
+
+        let work_area = info.rcWork;
+        let width = dpi.scale(self.size.width);
+        let height = dpi.scale(self.size.height);
+        let x = work_area.left + ((work_area.right - work_area.left) - width) / 2;
+        let y = work_area.top + ((work_area.bottom - work_area.top) - height) / 2;
+
+        self.position = Some(Point2D::new(x, y));
+        self
+    }
+
+    /// Sets the initial window theme.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Constrains how small the user may resize the window.
+    pub fn with_min_size(mut self, size: Size2D<i32>) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    /// Constrains how large the user may resize the window.
+    pub fn with_max_size(mut self, size: Size2D<i32>) -> Self {
+        self.max_size = Some(size);
+        self
+    }
+
+    /// Sets the initial title bar background color (Windows 11 only).
+    pub fn with_caption_color(mut self, color: Color) -> Self {
+        self.caption_color = Some(color);
+        self
+    }
+
+    /// Sets the initial window border color (Windows 11 only).
+    pub fn with_border_color(mut self, color: Color) -> Self {
+        self.border_color = Some(color);
+        self
+    }
+
+    /// Sets the initial title bar text color (Windows 11 only).
+    pub fn with_caption_text_color(mut self, color: Color) -> Self {
+        self.caption_text_color = Some(color);
+        self
+    }
+
+    /// Sets the system backdrop material (Windows 11 only).
+    pub fn with_backdrop(mut self, backdrop: Backdrop) -> Self {
+        self.backdrop = backdrop;
+        self
+    }
+
+    /// Sets the window's corner rounding preference (Windows 11 only).
+    pub fn with_corner_preference(mut self, preference: CornerPreference) -> Self {
+        self.corner_preference = preference;
+        self
+    }
+
+    /// Controls whether the window keeps its standard title bar and border.
+    /// Pass `false` for a caption-less window that draws its own title bar;
+    /// register [`Window::set_drag_regions`] and
+    /// [`Window::set_caption_buttons`] so the custom chrome still drags,
+    /// resizes, and snaps like a normal window.
+    pub fn with_decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Fills the client area with `color` whenever it's erased (e.g. on
+    /// first show or resize), via `WM_ERASEBKGND`, instead of leaving it to
+    /// whatever the OS paints by default. Avoids the white flash that
+    /// otherwise appears before the first paint.
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Runs a `SetTimer`-driven paint tick for the duration of the modal
+    /// resize/move loop, so renderers that only redraw in response to
+    /// [`Event::Paint`](crate::event_loop::Event::Paint) keep drawing while
+    /// the user drags an edge, instead of freezing until the drag ends.
+    pub fn with_live_resize(mut self, live_resize: bool) -> Self {
+        self.live_resize = live_resize;
+        self
+    }
+
+    /// Sets whether the window starts out always-on-top, staying above all
+    /// other non-topmost windows. See [`Window::set_always_on_top`].
+    pub fn with_always_on_top(mut self, always_on_top: bool) -> Self {
+        self.always_on_top = always_on_top;
+        self
+    }
+
+    /// Hides the window from the taskbar, via `WS_EX_TOOLWINDOW`. See also
+    /// [`Builder::with_tool_window`] for utility palettes that also
+    /// shouldn't steal focus when shown.
+    pub fn with_skip_taskbar(mut self, skip_taskbar: bool) -> Self {
+        self.skip_taskbar = skip_taskbar;
+        self
+    }
+
+    /// Gives the window the full tool-window treatment: hidden from the
+    /// taskbar and Alt+Tab switcher and never activated when shown, via
+    /// `WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE`. Suited to utility palettes
+    /// and notification popups that shouldn't clutter the taskbar or steal
+    /// focus from the window the user is working in.
+    pub fn with_tool_window(mut self, tool_window: bool) -> Self {
+        self.tool_window = tool_window;
+        self
+    }
+
+    /// Sets the window's initial opacity. See [`Window::set_opacity`].
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Creates the window with `WS_EX_NOREDIRECTIONBITMAP`, opting it out of
+    /// the DWM's own redirection surface so its content can instead be
+    /// supplied by a DirectComposition visual tree bound to it via
+    /// [`crate::graphics::composition::Compositor::create_target`].
+    pub fn with_composition_target(mut self, composition_target: bool) -> Self {
+        self.composition_target = composition_target;
+        self
+    }
+
+    /// Controls whether the user can resize the window by dragging its
+    /// edges, via `WS_THICKFRAME`, and whether its maximize button is
+    /// present, via `WS_MAXIMIZEBOX`. Defaults to `true`.
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Sets the state the window is shown in immediately after creation.
+    /// Defaults to [`InitialState::Normal`].
+    pub fn with_initial_state(mut self, initial_state: InitialState) -> Self {
+        self.initial_state = initial_state;
+        self
+    }
+
+    /// OR's extra raw `WS_*`/`WS_EX_*` bits into the style/ex-style passed
+    /// to `CreateWindowExW`, for styles this crate doesn't yet expose as a
+    /// dedicated `Builder` option (e.g. `WindowExStyle::ACCEPT_FILES`).
+    /// Applied on top of whatever styles the other `with_*` methods already
+    /// contribute, so it can't be used to turn those off — use the specific
+    /// `with_*` method instead when one exists.
+    pub fn with_style_overrides(mut self, style: WindowStyle, ex_style: WindowExStyle) -> Self {
+        self.style_overrides = style;
+        self.ex_style_overrides = ex_style;
+        self
+    }
+
+    /// Sets the cursor shown while the pointer is over the window's client
+    /// area. Defaults to the standard arrow.
+    pub fn with_cursor(mut self, cursor: SystemCursor) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    /// Sets the window's title bar, taskbar, and `Alt+Tab` icon. See
+    /// [`Window::set_icon`].
+    pub fn with_icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Subscribes the window to `WM_DEVICECHANGE` arrival/removal
+    /// notifications for each [`DeviceClass`] in `classes`, retrievable via
+    /// [`Window::drain_device_events`].
+    #[cfg(feature = "device_notifications")]
+    pub fn with_device_notifications(mut self, classes: &[DeviceClass]) -> Self {
+        self.device_notifications = classes.to_vec();
+        self
+    }
+
+    /// Supplies a [`WindowHandler`] that is invoked directly from the
+    /// window procedure, as an alternative to polling `Window::is_requesting_*`.
+    pub fn with_handler(mut self, handler: impl WindowHandler + 'static) -> Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Creates the window as a `WS_CHILD` of `parent`, clipped to and
+    /// positioned relative to `parent`'s client area rather than the
+    /// desktop. Windows destroys child windows automatically when their
+    /// parent is destroyed, so a child outliving its parent in Rust (e.g.
+    /// because the application held on to both `Window`s and dropped the
+    /// parent first) just means its own later `DestroyWindow` call becomes
+    /// a harmless no-op. Overrides any earlier [`Builder::with_owner`]
+    /// call, since a window can only be a child or an owned window, not
+    /// both.
+    pub fn with_parent(mut self, parent: &Window) -> Self {
+        self.parent = Some(parent.hwnd());
+        self.owner = None;
+        self
+    }
+
+    /// Creates the window as a top-level window owned by `owner`, e.g. a
+    /// tool palette or floating panel that should stay in front of
+    /// `owner` and minimize/restore alongside it, without becoming a
+    /// `WS_CHILD`. Unlike a child window, Windows does not destroy an
+    /// owned window when its owner is destroyed; the owned [`Window`]
+    /// should be dropped (or otherwise destroyed) before its owner to
+    /// avoid leaving a dangling owner reference. Overrides any earlier
+    /// [`Builder::with_parent`] call, since a window can only be a child
+    /// or an owned window, not both.
+    pub fn with_owner(mut self, owner: &Window) -> Self {
+        self.owner = Some(owner.hwnd());
+        self.parent = None;
+        self
+    }
+
+    /// Creates the window.
+    pub fn build(self) -> Result<Window> {
+        class::register();
+
+        let inner = Box::new(WindowInner::new(
+            self.theme,
+            self.size,
+            self.handler,
+            self.min_size,
+            self.max_size,
+            self.decorations,
+            self.background,
+            self.live_resize,
+        ));
+        let inner_ptr = Box::into_raw(inner);
+
+        let title = encode_wide(&self.title);
+
+        let (x, y) = match self.position {
+            Some(position) => (position.x, position.y),
+            None => (CW_USEDEFAULT, CW_USEDEFAULT),
+        };
+
+        let mut style = match self.parent {
+            Some(_) => WS_CHILD,
+            None => WS_OVERLAPPEDWINDOW,
+        };
+        if !self.resizable {
+            style &= !(WS_THICKFRAME | WS_MAXIMIZEBOX);
+        }
+        style |= self.style_overrides.0;
+        let hwndparent = self.parent.or(self.owner);
+
+        let mut ex_style = Default::default();
+        if self.skip_taskbar || self.tool_window {
+            ex_style |= WS_EX_TOOLWINDOW;
+        }
+        if self.tool_window {
+            ex_style |= WS_EX_NOACTIVATE;
+        }
+        if self.composition_target {
+            ex_style |= WS_EX_NOREDIRECTIONBITMAP;
+        }
+        ex_style |= self.ex_style_overrides.0;
+
+        // SAFETY: `inner_ptr` is a uniquely-owned pointer handed to the
+        // window procedure via `lpParam`; it is reclaimed into a `Box`
+        // exactly once below, whether creation succeeds or fails.
+        let hwnd = unsafe {
+            CreateWindowExW(
+                ex_style,
+                class::CLASS_NAME,
+                PCWSTR(title.as_ptr()),
+                style,
+                x,
+                y,
+                self.size.width,
+                self.size.height,
+                hwndparent,
+                None,
+                None,
+                Some(inner_ptr.cast()),
+            )
+        };
+
+        let hwnd = match hwnd {
+            Ok(hwnd) => hwnd,
+            Err(err) => {
+                // SAFETY: creation failed before the window procedure could
+                // observe `inner_ptr`, so we still hold sole ownership.
+                unsafe {
+                    drop(Box::from_raw(inner_ptr));
+                }
+                return Err(Error::from(err));
+            }
+        };
+
+        // SAFETY: `inner_ptr` was stashed in `GWLP_USERDATA` during
+        // `WM_NCCREATE` and is reclaimed here exactly once.
+        let inner = unsafe { Box::from_raw(inner_ptr) };
+        register_window();
+
+        self.theme.apply(hwnd);
+        self.backdrop.apply(hwnd);
+        self.corner_preference.apply(hwnd);
+
+        let window = Window { hwnd, inner };
+        if let Some(color) = self.caption_color {
+            window.set_caption_color(color);
+        }
+        if let Some(color) = self.border_color {
+            window.set_border_color(color);
+        }
+        if let Some(color) = self.caption_text_color {
+            window.set_caption_text_color(color);
+        }
+        window.set_cursor(self.cursor)?;
+        if let Some(icon) = self.icon {
+            window.set_icon(icon);
+        }
+        if self.always_on_top {
+            window.set_always_on_top(true)?;
+        }
+        if let Some(opacity) = self.opacity {
+            window.set_opacity(opacity)?;
+        }
+        #[cfg(feature = "device_notifications")]
+        if !self.device_notifications.is_empty() {
+            window
+                .inner
+                .register_device_notifications(hwnd, &self.device_notifications)?;
+        }
+
+        // SAFETY: `hwnd` was just created and is being shown for the first
+        // time.
+        unsafe {
+            let _ = ShowWindow(hwnd, self.initial_state.show_cmd());
+        }
+
+        window.inner.dpi.set(Dpi::detect(hwnd));
+
+        Ok(window)
+    }
+}
@@ -0,0 +1,80 @@
+//! Typed wrappers around the raw `WS_*`/`WS_EX_*` bitmasks, letting callers
+//! opt into style bits this crate doesn't yet model as a dedicated
+//! [`crate::window::Builder`] option, without depending on the `windows`
+//! crate themselves.
+
+use std::ops::BitOr;
+
+use windows::Win32::UI::WindowsAndMessaging::{
+    WINDOW_EX_STYLE, WINDOW_STYLE, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_CLIENTEDGE,
+    WS_EX_COMPOSITED,
+};
+
+/// A `WS_*` window style bitmask, as passed to `CreateWindowExW`'s
+/// `dwStyle` parameter. Set via
+/// [`Builder::with_style_overrides`](crate::window::Builder::with_style_overrides).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WindowStyle(pub(crate) WINDOW_STYLE);
+
+impl WindowStyle {
+    /// An empty style, contributing no additional bits.
+    pub const NONE: Self = Self(WINDOW_STYLE(0));
+
+    /// Wraps a raw `WS_*` bitmask not otherwise modeled here.
+    pub const fn from_raw(bits: u32) -> Self {
+        Self(WINDOW_STYLE(bits))
+    }
+
+    /// Returns `true` if `self` contains every bit set in `other`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0.contains(other.0)
+    }
+}
+
+impl BitOr for WindowStyle {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A `WS_EX_*` extended window style bitmask, as passed to
+/// `CreateWindowExW`'s `dwExStyle` parameter. Set via
+/// [`Builder::with_style_overrides`](crate::window::Builder::with_style_overrides).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WindowExStyle(pub(crate) WINDOW_EX_STYLE);
+
+impl WindowExStyle {
+    /// An empty style, contributing no additional bits.
+    pub const NONE: Self = Self(WINDOW_EX_STYLE(0));
+    /// `WS_EX_ACCEPTFILES`: accepts `WM_DROPFILES` messages from Explorer.
+    pub const ACCEPT_FILES: Self = Self(WS_EX_ACCEPTFILES);
+    /// `WS_EX_APPWINDOW`: forces a taskbar button even for a child or owned
+    /// window that would otherwise not get one.
+    pub const APP_WINDOW: Self = Self(WS_EX_APPWINDOW);
+    /// `WS_EX_CLIENTEDGE`: a sunken border around the client area.
+    pub const CLIENT_EDGE: Self = Self(WS_EX_CLIENTEDGE);
+    /// `WS_EX_COMPOSITED`: double-buffers the window and its children so
+    /// they redraw as a single unit, avoiding flicker in complex child
+    /// hierarchies.
+    pub const COMPOSITED: Self = Self(WS_EX_COMPOSITED);
+
+    /// Wraps a raw `WS_EX_*` bitmask not otherwise modeled here.
+    pub const fn from_raw(bits: u32) -> Self {
+        Self(WINDOW_EX_STYLE(bits))
+    }
+
+    /// Returns `true` if `self` contains every bit set in `other`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0.contains(other.0)
+    }
+}
+
+impl BitOr for WindowExStyle {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
@@ -0,0 +1,161 @@
+//! Attaching skylight's keyboard and event machinery to a window created by
+//! another framework, via `SetWindowSubclass` rather than replacing its
+//! window procedure outright.
+
+use std::cell::Ref;
+
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::UI::Shell::{DefSubclassProc, RemoveWindowSubclass, SetWindowSubclass};
+use windows::Win32::UI::WindowsAndMessaging::{WM_DESTROY, WM_NCDESTROY};
+
+use crate::dpi::Dpi;
+use crate::error::{Error, Result};
+use crate::geometry::Size2D;
+use crate::keyboard::{KeyCode, Keyboard};
+use crate::mouse::Mouse;
+use crate::theme::Theme;
+
+use super::inner::WindowInner;
+
+/// The subclass ID skylight registers via `SetWindowSubclass`. A single
+/// constant is enough since at most one skylight subclass is ever attached
+/// to a given `hwnd`.
+const SUBCLASS_ID: usize = 1;
+
+/// Skylight's keyboard, mouse, and event machinery, attached to a window
+/// created by another framework (e.g. a host application embedding a
+/// skylight-based plugin) via `SetWindowSubclass`, so its own window
+/// procedure keeps handling messages as before and skylight merely
+/// observes them alongside it.
+///
+/// Dropping a `Subclass` removes it via `RemoveWindowSubclass`, leaving
+/// `hwnd` exactly as the host framework left it; it does not destroy
+/// `hwnd`, since skylight never owned it.
+pub struct Subclass {
+    hwnd: HWND,
+    inner: Box<WindowInner>,
+}
+
+impl Subclass {
+    /// Attaches skylight's keyboard and event machinery to `hwnd` as an
+    /// additional `SetWindowSubclass` subclass.
+    pub fn attach(hwnd: HWND) -> Result<Self> {
+        let inner = Box::new(WindowInner::new(
+            Theme::default(),
+            Size2D::default(),
+            None,
+            None,
+            None,
+            true,
+            None,
+            false,
+        ));
+        let inner_ptr = Box::into_raw(inner);
+
+        // SAFETY: `hwnd` is a valid, live window owned by the caller for at
+        // least the lifetime of the returned `Subclass`; `inner_ptr` is
+        // handed over as `dwRefData` and reclaimed into a `Box` exactly
+        // once below, whether attaching succeeds or fails.
+        let attached = unsafe {
+            SetWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID, inner_ptr as usize)
+        };
+
+        if !attached.as_bool() {
+            // SAFETY: `SetWindowSubclass` failed, so `subclass_proc` never
+            // observed `inner_ptr`; we still hold sole ownership.
+            unsafe {
+                drop(Box::from_raw(inner_ptr));
+            }
+            return Err(Error::from(windows::core::Error::from_win32()));
+        }
+
+        // SAFETY: `inner_ptr` was just handed to `SetWindowSubclass` above,
+        // which only ever borrows it for the duration of each callback; we
+        // reclaim ownership here, exactly like `Builder::build` does for
+        // its own `GWLP_USERDATA` pointer.
+        let inner = unsafe { Box::from_raw(inner_ptr) };
+        inner.dpi.set(Dpi::detect(hwnd));
+
+        Ok(Self { hwnd, inner })
+    }
+
+    /// The subclassed window's raw handle.
+    pub fn hwnd(&self) -> HWND {
+        self.hwnd
+    }
+
+    /// The DPI detected when the subclass was attached.
+    pub fn dpi(&self) -> Dpi {
+        self.inner.dpi.get()
+    }
+
+    /// Grants read access to keyboard state for this window.
+    pub fn keyboard(&self) -> Ref<'_, Keyboard> {
+        self.inner.keyboard.borrow()
+    }
+
+    /// Grants read access to mouse state for this window.
+    pub fn mouse(&self) -> Ref<'_, Mouse> {
+        self.inner.mouse.borrow()
+    }
+
+    /// Returns `true` if the window currently has keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.inner.is_focused()
+    }
+
+    /// Removes and returns all key transitions accumulated since the last
+    /// call.
+    pub fn drain_key_transitions(&self) -> Vec<(KeyCode, bool)> {
+        self.inner.drain_key_transitions()
+    }
+
+    /// Removes and returns the most recent size reported by `WM_SIZE`, if
+    /// any, since the last call.
+    pub fn take_resize(&self) -> Option<Size2D<i32>> {
+        self.inner.take_resize()
+    }
+}
+
+impl Drop for Subclass {
+    fn drop(&mut self) {
+        // SAFETY: `self.hwnd` is still valid; removing a subclass that has
+        // already removed itself (e.g. because the window was destroyed,
+        // see `subclass_proc`'s `WM_NCDESTROY` handling) is a harmless
+        // no-op.
+        unsafe {
+            let _ = RemoveWindowSubclass(self.hwnd, Some(subclass_proc), SUBCLASS_ID);
+        }
+    }
+}
+
+/// The subclass procedure skylight registers via `SetWindowSubclass`.
+unsafe extern "system" fn subclass_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _uidsubclass: usize,
+    dwrefdata: usize,
+) -> LRESULT {
+    if msg == WM_NCDESTROY {
+        // The host window is being destroyed; detach now so Windows can't
+        // call back into a `WindowInner` that `Subclass::drop` may free at
+        // any point afterwards.
+        let _ = RemoveWindowSubclass(hwnd, Some(subclass_proc), SUBCLASS_ID);
+        return DefSubclassProc(hwnd, msg, wparam, lparam);
+    }
+
+    // `WindowInner::handle_message`'s `WM_DESTROY` handling assumes it
+    // owns the window's place in skylight's global live-window count (see
+    // `super::inner::LIVE_WINDOWS`), which a merely-subclassed, foreign
+    // window never joined; skip straight to the host's own handling.
+    if msg != WM_DESTROY {
+        let inner = dwrefdata as *const WindowInner;
+        if let Some(result) = (*inner).handle_message(hwnd, msg, wparam, lparam) {
+            return result;
+        }
+    }
+
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}